@@ -0,0 +1,63 @@
+use crate::commands::hooks_audit::{audit_hooks, format_report};
+use crate::utils::hook_audit::RiskLevel;
+
+const USAGE: &str = "Usage: claude-skills-manager audit-hooks [--json] [chemin_projet...]";
+
+/// Sous-commandes en ligne de commande ; None si aucune n'est reconnue (lancement de l'interface)
+///
+/// `audit-hooks` audite les hooks globaux et ceux des projets donnés (dossier courant par défaut).
+/// Code de sortie : 0 si aucun risque élevé, 2 si un hook est à risque `high` ou `critical`.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("audit-hooks") => Some(audit_hooks_cli(&args[1..])),
+        Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Some(0)
+        }
+        _ => None,
+    }
+}
+
+fn audit_hooks_cli(args: &[String]) -> i32 {
+    let json = args.iter().any(|a| a == "--json");
+    let mut project_paths: Vec<String> = args.iter().filter(|a| !a.starts_with("--")).cloned().collect();
+    if project_paths.is_empty() {
+        if let Ok(cwd) = std::env::current_dir() {
+            project_paths.push(cwd.to_string_lossy().to_string());
+        }
+    }
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Erreur démarrage runtime: {}", e);
+            return 1;
+        }
+    };
+    let audits = match runtime.block_on(audit_hooks(project_paths)) {
+        Ok(audits) => audits,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&audits) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Erreur sérialisation: {}", e);
+                return 1;
+            }
+        }
+    } else {
+        print!("{}", format_report(&audits));
+    }
+
+    let worst = audits.iter().map(|a| a.risk_level).max().unwrap_or(RiskLevel::None);
+    if worst >= RiskLevel::High {
+        2
+    } else {
+        0
+    }
+}
@@ -1,12 +1,28 @@
+use crate::models::skill::{Skill, SkillSource};
+use crate::utils::injection_scanner::scan_skill_cached;
+use crate::utils::markdown_outline::analyze_markdown;
+use crate::utils::script_checks::check_scripts;
+use crate::utils::tokens::estimate_tokens;
+use crate::utils::triggers::{cosine_similarity, extract_trigger_phrases, shared_phrases};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use super::skills::list_skills;
+
+/// Score de similarité à partir duquel deux skills sont signalés comme concurrents
+const OVERLAP_THRESHOLD: f64 = 0.5;
+
+/// Pénalité cumulée maximale par code (structure markdown, scripts, sécurité) : vingt liens
+/// cassés ne comptent pas plus qu'une erreur
+const MAX_PENALTY_PER_CODE: i32 = 30;
+
 #[derive(Serialize, Deserialize)]
 pub struct Issue {
     pub severity: String,   // "error" | "warning" | "info"
-    pub category: String,   // "description" | "naming" | "structure" | "instructions" | "frontmatter"
+    pub category: String,   // "description" | "naming" | "structure" | "instructions" | "frontmatter" | "scripts" | "security"
     pub code: String,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -36,6 +52,8 @@ pub struct SkillStructure {
     pub folders: Vec<String>,
     pub total_size_kb: f64,
     pub instruction_word_count: usize,
+    pub frontmatter_tokens: usize,   // Estimation tokens du frontmatter YAML
+    pub body_tokens: usize,          // Estimation tokens des instructions (chargées à l'utilisation)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -123,6 +141,8 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
             folders,
             total_size_kb,
             instruction_word_count: 0,
+            frontmatter_tokens: 0,
+            body_tokens: 0,
         };
         let status = compute_status(score);
         return Ok(SkillAnalysis {
@@ -171,6 +191,8 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
         .map_err(|e| format!("Erreur lecture SKILL.md: {}", e))?;
 
     let instruction_word_count = count_words(&raw_content);
+    let frontmatter_tokens = estimate_tokens(&extract_frontmatter_string(&raw_content));
+    let body_tokens = estimate_tokens(extract_body_string(&raw_content));
 
     let (frontmatter_value, yaml_valid) = parse_frontmatter_raw(&raw_content);
 
@@ -443,9 +465,11 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
         });
     }
 
+    // Plan du Markdown (titres de tous niveaux, hors blocs de code)
+    let outline = analyze_markdown(&raw_content, &folder);
+
     // MISSING_EXAMPLES
-    let lower_content = raw_content.to_lowercase();
-    if !lower_content.contains("## examples") && !lower_content.contains("## exemples") {
+    if !outline.has_section(&["examples", "exemples"]) {
         score -= 10;
         issues.push(Issue {
             severity: "warning".into(),
@@ -474,7 +498,7 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
     }
 
     // MISSING_TROUBLESHOOTING
-    if !lower_content.contains("## troubleshooting") && !lower_content.contains("## dépannage") {
+    if !outline.has_section(&["troubleshooting", "dépannage"]) {
         score -= 10;
         issues.push(Issue {
             severity: "warning".into(),
@@ -486,6 +510,46 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
         });
     }
 
+    // UNCLOSED_CODE_FENCE, BROKEN_LINK, EMPTY_SECTION, HEADING_LEVEL_JUMP
+    let mut penalties: HashMap<&str, i32> = HashMap::new();
+    for finding in outline.findings {
+        score -= capped_penalty(&mut penalties, finding.code, finding.severity);
+        issues.push(Issue {
+            severity: finding.severity.into(),
+            category: if finding.code == "BROKEN_LINK" { "structure".into() } else { "instructions".into() },
+            code: finding.code.into(),
+            message: finding.message,
+            current_value: finding.current_value,
+            location: Some(format!("SKILL.md:{}:{}", finding.line, finding.column)),
+        });
+    }
+
+    // --- Scripts ---
+    for finding in check_scripts(&folder, &raw_content) {
+        score -= capped_penalty(&mut penalties, finding.code, finding.severity);
+        issues.push(Issue {
+            severity: finding.severity.into(),
+            category: "scripts".into(),
+            code: finding.code.into(),
+            location: Some(finding.location()),
+            message: finding.message,
+            current_value: finding.current_value,
+        });
+    }
+
+    // --- Sécurité (instructions injectées, texte masqué) ---
+    for finding in scan_skill_cached(&folder) {
+        score -= capped_penalty(&mut penalties, finding.code, finding.severity);
+        issues.push(Issue {
+            severity: finding.severity.into(),
+            category: "security".into(),
+            code: finding.code.into(),
+            location: Some(finding.location()),
+            message: finding.message,
+            current_value: finding.current_value,
+        });
+    }
+
     // --- Infos ---
 
     // MISSING_LICENSE
@@ -568,6 +632,8 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
         folders,
         total_size_kb,
         instruction_word_count,
+        frontmatter_tokens,
+        body_tokens,
     };
 
     let status = compute_status(score);
@@ -585,6 +651,195 @@ pub async fn analyze_skill(path: String) -> Result<SkillAnalysis, String> {
     })
 }
 
+/// Référence légère vers un skill dans un rapport de collisions
+#[derive(Serialize, Deserialize)]
+pub struct SkillRef {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub source: SkillSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+}
+
+impl From<&Skill> for SkillRef {
+    fn from(skill: &Skill) -> Self {
+        Self {
+            id: skill.id.clone(),
+            name: skill.name.clone(),
+            path: skill.path.clone(),
+            source: skill.source.clone(),
+            project_name: skill.project_name.clone(),
+        }
+    }
+}
+
+/// Deux skills visibles ensemble dont les descriptions se disputent les mêmes déclencheurs
+#[derive(Serialize, Deserialize)]
+pub struct SkillOverlap {
+    pub scope: String,      // "global" ou nom du projet
+    pub severity: String,   // "warning" | "info"
+    pub skill_a: SkillRef,
+    pub skill_b: SkillRef,
+    pub similarity: f64,
+    pub description_similarity: f64,
+    pub trigger_similarity: f64,
+    pub shared_triggers: Vec<String>,
+}
+
+/// Plusieurs skills visibles ensemble portant le même `name`
+#[derive(Serialize, Deserialize)]
+pub struct DuplicateName {
+    pub scope: String,
+    pub name: String,
+    pub skills: Vec<SkillRef>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CollisionReport {
+    pub overlaps: Vec<SkillOverlap>,
+    pub duplicate_names: Vec<DuplicateName>,
+}
+
+/// Détecte les skills susceptibles de se masquer mutuellement
+///
+/// Un projet voit les skills globaux et les siens : les comparaisons sont faites au sein
+/// des skills globaux, puis pour chaque projet entre ses skills et ceux visibles avec eux.
+#[tauri::command]
+pub async fn analyze_skill_collisions(project_paths: Vec<String>) -> Result<CollisionReport, String> {
+    let skills = list_skills(project_paths).await?;
+
+    let global: Vec<&Skill> = skills
+        .iter()
+        .filter(|s| matches!(s.source, SkillSource::Global))
+        .collect();
+    let mut by_project: HashMap<String, Vec<&Skill>> = HashMap::new();
+    for skill in skills.iter().filter(|s| matches!(s.source, SkillSource::Project)) {
+        let project = skill.project_name.clone().unwrap_or_else(|| "Unknown".into());
+        by_project.entry(project).or_default().push(skill);
+    }
+
+    let triggers: HashMap<&str, Vec<String>> = skills
+        .iter()
+        .map(|s| (s.id.as_str(), extract_trigger_phrases(&s.description)))
+        .collect();
+
+    let mut overlaps = Vec::new();
+    let mut duplicate_names = Vec::new();
+
+    // Portée globale
+    for (i, a) in global.iter().enumerate() {
+        for b in &global[i + 1..] {
+            if let Some(overlap) = compare_skills("global", a, b, &triggers) {
+                overlaps.push(overlap);
+            }
+        }
+    }
+    duplicate_names.extend(find_duplicate_names("global", &global, &[]));
+
+    // Portée de chaque projet : ses skills entre eux et face aux skills globaux
+    let mut projects: Vec<_> = by_project.into_iter().collect();
+    projects.sort_by(|a, b| a.0.cmp(&b.0));
+    for (project, own) in &projects {
+        for (i, a) in own.iter().enumerate() {
+            for b in own[i + 1..].iter().chain(global.iter()) {
+                if let Some(overlap) = compare_skills(project, a, b, &triggers) {
+                    overlaps.push(overlap);
+                }
+            }
+        }
+        duplicate_names.extend(find_duplicate_names(project, own, &global));
+    }
+
+    overlaps.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    Ok(CollisionReport {
+        overlaps,
+        duplicate_names,
+    })
+}
+
+fn compare_skills(
+    scope: &str,
+    a: &Skill,
+    b: &Skill,
+    triggers: &HashMap<&str, Vec<String>>,
+) -> Option<SkillOverlap> {
+    // Un symlink et sa cible sont le même skill
+    if a.id == b.id || same_folder(&a.path, &b.path) {
+        return None;
+    }
+
+    let (ta, tb) = (&triggers[a.id.as_str()], &triggers[b.id.as_str()]);
+    let description_similarity = cosine_similarity(&a.description, &b.description);
+    let shared_triggers = shared_phrases(ta, tb);
+    let trigger_similarity = if ta.is_empty() || tb.is_empty() {
+        0.0
+    } else {
+        shared_triggers.len() as f64 / ta.len().min(tb.len()) as f64
+    };
+
+    let similarity = if ta.is_empty() || tb.is_empty() {
+        description_similarity
+    } else {
+        0.6 * trigger_similarity + 0.4 * description_similarity
+    };
+
+    if similarity < OVERLAP_THRESHOLD && shared_triggers.is_empty() {
+        return None;
+    }
+
+    Some(SkillOverlap {
+        scope: scope.to_string(),
+        severity: if similarity >= 0.7 { "warning".into() } else { "info".into() },
+        skill_a: a.into(),
+        skill_b: b.into(),
+        similarity,
+        description_similarity,
+        trigger_similarity,
+        shared_triggers,
+    })
+}
+
+fn same_folder(a: &str, b: &str) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(ca), Ok(cb)) => ca == cb,
+        _ => false,
+    }
+}
+
+/// Noms en double parmi `own`, ou entre `own` et `others` (les doublons internes à `others` sont ignorés)
+fn find_duplicate_names(scope: &str, own: &[&Skill], others: &[&Skill]) -> Vec<DuplicateName> {
+    let mut groups: HashMap<String, Vec<&Skill>> = HashMap::new();
+    for skill in own.iter().chain(others.iter()) {
+        groups.entry(skill.name.to_lowercase()).or_default().push(skill);
+    }
+
+    let own_ids: Vec<&str> = own.iter().map(|s| s.id.as_str()).collect();
+    let mut duplicates: Vec<DuplicateName> = groups
+        .into_values()
+        .map(|mut group| {
+            // Un symlink vers un skill global n'est pas un doublon
+            let mut seen: Vec<PathBuf> = Vec::new();
+            group.retain(|s| {
+                let canonical = fs::canonicalize(&s.path).unwrap_or_else(|_| PathBuf::from(&s.path));
+                let first = !seen.contains(&canonical);
+                seen.push(canonical);
+                first
+            });
+            group
+        })
+        .filter(|group| group.len() > 1 && group.iter().any(|s| own_ids.contains(&s.id.as_str())))
+        .map(|group| DuplicateName {
+            scope: scope.to_string(),
+            name: group[0].name.clone(),
+            skills: group.into_iter().map(SkillRef::from).collect(),
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+    duplicates
+}
+
 fn compute_status(score: i32) -> String {
     if score >= 80 {
         "valid".into()
@@ -595,6 +850,23 @@ fn compute_status(score: i32) -> String {
     }
 }
 
+/// Points retirés au score selon la sévérité d'un problème
+/// Pénalité d'un constat, plafonnée au cumul `MAX_PENALTY_PER_CODE` pour son code
+fn capped_penalty<'a>(spent: &mut HashMap<&'a str, i32>, code: &'a str, severity: &str) -> i32 {
+    let spent = spent.entry(code).or_default();
+    let penalty = severity_penalty(severity).min(MAX_PENALTY_PER_CODE - *spent);
+    *spent += penalty;
+    penalty
+}
+
+fn severity_penalty(severity: &str) -> i32 {
+    match severity {
+        "error" => 30,
+        "warning" => 10,
+        _ => 5,
+    }
+}
+
 fn count_words(text: &str) -> usize {
     text.split_whitespace().count()
 }
@@ -610,7 +882,7 @@ fn dir_size_kb(path: &PathBuf) -> f64 {
     bytes as f64 / 1024.0
 }
 
-fn is_generic_description(desc: &str) -> bool {
+pub fn is_generic_description(desc: &str) -> bool {
     let lower = desc.to_lowercase();
     let generic_phrases = [
         "helps with",
@@ -662,6 +934,21 @@ fn extract_frontmatter_string(content: &str) -> String {
         .unwrap_or_default()
 }
 
+/// Contenu Markdown après le frontmatter (tout le contenu s'il n'y en a pas)
+pub fn extract_body_string(content: &str) -> &str {
+    let content = content.trim();
+    if !content.starts_with("---") {
+        return content;
+    }
+    match content[3..].find("\n---") {
+        Some(pos) => {
+            let after = &content[pos + 3 + 4..];
+            after.find('\n').map(|nl| &after[nl + 1..]).unwrap_or("")
+        }
+        None => content,
+    }
+}
+
 fn to_kebab_case(s: &str) -> String {
     s.to_lowercase()
         .chars()
@@ -673,7 +960,7 @@ fn to_kebab_case(s: &str) -> String {
         .join("-")
 }
 
-fn detect_category(name: &str, description: &str) -> String {
+pub fn detect_category(name: &str, description: &str) -> String {
     let text = format!("{} {}", name, description).to_lowercase();
     if text.contains("mcp") || text.contains("plugin") || text.contains("server") {
         "mcp-enhancement".into()
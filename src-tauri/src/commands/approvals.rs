@@ -0,0 +1,225 @@
+use crate::models::config::Config;
+use crate::utils::approvals::{
+    compare, config_hash, is_empty_config, unified_diff, Approval, ApprovalLedger, ConfigChange, Quarantine,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::settings::{merge_and_write, read_json_file};
+use super::CLAUDE_DIR;
+
+/// État de revue de la configuration exécutable d'un projet
+#[derive(Debug, Serialize)]
+pub struct ProjectReview {
+    pub project_path: String,
+    pub name: String,
+    pub hash: String,
+    pub approved_hash: Option<String>,
+    pub approved_at: Option<u64>,
+    pub pending: bool,
+    pub quarantined: bool,
+    pub changes: Vec<ConfigChange>,
+    pub diff: String,
+}
+
+/// Hooks (`.claude/settings.json`) et serveurs MCP (`.mcp.json`) d'un projet
+pub fn executable_config(project: &Path) -> Value {
+    let settings = read_json_file(&project.join(CLAUDE_DIR).join("settings.json"));
+    let mcp = read_json_file(&project.join(".mcp.json"));
+    json!({
+        "hooks": settings.get("hooks").cloned().unwrap_or(json!({})),
+        "mcpServers": mcp.get("mcpServers").cloned().unwrap_or(json!({})),
+    })
+}
+
+/// Vrai si le projet définit des hooks ou serveurs jamais approuvés ou modifiés depuis
+pub fn needs_review(ledger: &ApprovalLedger, project: &Path) -> bool {
+    let config = executable_config(project);
+    if is_empty_config(&config) {
+        return false;
+    }
+    let hash = config_hash(&config);
+    ledger
+        .projects
+        .get(&ledger_key(project))
+        .and_then(|entry| entry.approved.as_ref())
+        .is_none_or(|approved| approved.hash != hash)
+}
+
+pub fn load_ledger() -> Result<ApprovalLedger, String> {
+    ApprovalLedger::load(&ledger_path()?).map_err(|e| format!("Erreur lecture registre d'approbations: {}", e))
+}
+
+/// Registre d'approbations, ou registre vide s'il est illisible : tous les projets ayant des
+/// hooks ou serveurs MCP redeviennent alors à revoir, sans bloquer le reste de l'application
+pub fn load_ledger_or_empty() -> ApprovalLedger {
+    load_ledger().unwrap_or_else(|_e| {
+        #[cfg(debug_assertions)]
+        eprintln!("⚠️ {} : registre ignoré", _e);
+        ApprovalLedger::default()
+    })
+}
+
+fn save_ledger(ledger: &ApprovalLedger) -> Result<(), String> {
+    ledger
+        .save(&ledger_path()?)
+        .map_err(|e| format!("Erreur écriture registre d'approbations: {}", e))
+}
+
+fn ledger_path() -> Result<PathBuf, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    Ok(PathBuf::from(config.expanded_approvals_path()))
+}
+
+/// Clé du registre : chemin canonique du projet
+fn ledger_key(project: &Path) -> String {
+    project
+        .canonicalize()
+        .unwrap_or_else(|_| project.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn review(ledger: &ApprovalLedger, project: &Path) -> ProjectReview {
+    let config = executable_config(project);
+    let entry = ledger.projects.get(&ledger_key(project));
+    let approved = entry.and_then(|e| e.approved.as_ref());
+    let empty = json!({ "hooks": {}, "mcpServers": {} });
+    let approved_config = approved.map(|a| &a.config).unwrap_or(&empty);
+    let hash = config_hash(&config);
+
+    let pending = !is_empty_config(&config) && approved.is_none_or(|a| a.hash != hash);
+    ProjectReview {
+        project_path: project.to_string_lossy().to_string(),
+        name: project
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Projet")
+            .to_string(),
+        approved_hash: approved.map(|a| a.hash.clone()),
+        approved_at: approved.map(|a| a.approved_at),
+        pending,
+        quarantined: entry.is_some_and(|e| e.quarantine.is_some()),
+        changes: if pending { compare(approved_config, &config) } else { Vec::new() },
+        diff: if pending { unified_diff(approved_config, &config) } else { String::new() },
+        hash,
+    }
+}
+
+/// Liste l'état de revue des projets ; les hooks/serveurs nouveaux ou modifiés sont détaillés avec un diff
+#[tauri::command]
+pub async fn review_project_configs(project_paths: Vec<String>) -> Result<Vec<ProjectReview>, String> {
+    let ledger = load_ledger()?;
+    Ok(project_paths
+        .iter()
+        .map(|p| review(&ledger, Path::new(p)))
+        .filter(|r| r.pending || r.approved_hash.is_some() || r.quarantined)
+        .collect())
+}
+
+/// Approuve la configuration exécutable revue et lève une éventuelle quarantaine
+///
+/// `hash` est celui de la revue affichée : si la configuration a changé depuis, rien n'est approuvé.
+#[tauri::command]
+pub async fn approve_project_config(project_path: String, hash: String) -> Result<ProjectReview, String> {
+    let project = PathBuf::from(&project_path);
+    let config = executable_config(&project);
+    let current = config_hash(&config);
+    if current != hash {
+        return Err("La configuration a changé depuis la revue : relancez la revue avant d'approuver".to_string());
+    }
+
+    let mut ledger = load_ledger()?;
+    let entry = ledger.projects.entry(ledger_key(&project)).or_default();
+
+    if let Some(quarantine) = entry.quarantine.take() {
+        lift_quarantine(&project, &quarantine)?;
+    }
+    entry.approved = Some(Approval { hash: current, approved_at: now(), config });
+    save_ledger(&ledger)?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("✅ Configuration exécutable approuvée: {}", project_path);
+
+    Ok(review(&ledger, &project))
+}
+
+/// Désactive les hooks et les serveurs `.mcp.json` du projet via `.claude/settings.local.json`
+#[tauri::command]
+pub async fn quarantine_project_config(project_path: String) -> Result<ProjectReview, String> {
+    let project = PathBuf::from(&project_path);
+    let mut ledger = load_ledger()?;
+    let entry = ledger.projects.entry(ledger_key(&project)).or_default();
+    if entry.quarantine.is_some() {
+        return Ok(review(&ledger, &project));
+    }
+
+    let local_path = project.join(CLAUDE_DIR).join("settings.local.json");
+    let local = read_json_file(&local_path);
+
+    // Seul ce qui est ajouté ici est mémorisé, pour restaurer l'état exact à l'approbation
+    let disabled_hooks = local.get("disableAllHooks").and_then(Value::as_bool) != Some(true);
+    if disabled_hooks {
+        merge_and_write(&local_path, "disableAllHooks", Value::Bool(true))?;
+    }
+
+    let mut disabled: Vec<String> = string_list(local.get("disabledMcpjsonServers"));
+    let servers = read_json_file(&project.join(".mcp.json"));
+    let disabled_servers: Vec<String> = servers
+        .get("mcpServers")
+        .and_then(Value::as_object)
+        .map(|s| s.keys().filter(|k| !disabled.contains(k)).cloned().collect())
+        .unwrap_or_default();
+    if !disabled_servers.is_empty() {
+        disabled.extend(disabled_servers.iter().cloned());
+        merge_and_write(&local_path, "disabledMcpjsonServers", json!(disabled))?;
+    }
+
+    entry.quarantine = Some(Quarantine { quarantined_at: now(), disabled_hooks, disabled_servers });
+    save_ledger(&ledger)?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("🚧 Configuration exécutable mise en quarantaine: {}", project_path);
+
+    Ok(review(&ledger, &project))
+}
+
+fn lift_quarantine(project: &Path, quarantine: &Quarantine) -> Result<(), String> {
+    let local_path = project.join(CLAUDE_DIR).join("settings.local.json");
+    if !local_path.exists() {
+        return Ok(());
+    }
+    let mut local = read_json_file(&local_path);
+    let Some(obj) = local.as_object_mut() else {
+        return Ok(());
+    };
+
+    if quarantine.disabled_hooks {
+        obj.remove("disableAllHooks");
+    }
+    let remaining: Vec<String> = string_list(obj.get("disabledMcpjsonServers"))
+        .into_iter()
+        .filter(|s| !quarantine.disabled_servers.contains(s))
+        .collect();
+    if remaining.is_empty() {
+        obj.remove("disabledMcpjsonServers");
+    } else {
+        obj.insert("disabledMcpjsonServers".to_string(), json!(remaining));
+    }
+
+    let content = serde_json::to_string_pretty(&local).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    std::fs::write(&local_path, content).map_err(|e| format!("Erreur écriture: {}", e))
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default()
+}
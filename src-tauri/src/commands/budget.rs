@@ -0,0 +1,172 @@
+use crate::models::skill::{Skill, SkillSource};
+use crate::utils::tokens::estimate_tokens;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::skills::list_skills;
+use super::slash_commands::list_commands;
+use super::CLAUDE_DIR;
+
+/// Taille de la fenêtre de contexte de référence pour les pourcentages
+const CONTEXT_WINDOW_TOKENS: usize = 200_000;
+
+#[derive(Serialize, Deserialize)]
+pub struct BudgetItem {
+    pub kind: String,   // "memory" | "skill_metadata" | "command_metadata" | "skill_body" | "reference" | "command_body"
+    pub label: String,
+    pub path: String,
+    pub tokens: usize,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct BudgetSection {
+    pub total_tokens: usize,
+    pub items: Vec<BudgetItem>,
+}
+
+impl BudgetSection {
+    fn push(&mut self, item: BudgetItem) {
+        self.total_tokens += item.tokens;
+        self.items.push(item);
+    }
+}
+
+/// Coût en contexte de la configuration Claude d'un projet
+#[derive(Serialize, Deserialize)]
+pub struct ProjectBudget {
+    pub project_name: String,
+    pub project_path: String,
+    pub context_window: usize,
+    /// Chargé à chaque session : CLAUDE.md + name/description des skills et commandes
+    pub session_start: BudgetSection,
+    pub session_start_percent: f64,
+    /// Chargé à la demande : corps des skills, fichiers référencés, corps des commandes
+    pub on_demand: BudgetSection,
+}
+
+/// Calcule, pour chaque projet, le coût en tokens de sa configuration
+/// au démarrage de session et au chargement des skills
+#[tauri::command]
+pub async fn context_budget(project_paths: Vec<String>) -> Result<Vec<ProjectBudget>, String> {
+    let home = std::env::var("HOME").map_err(|_| "Variable HOME non définie".to_string())?;
+    let global_memory = PathBuf::from(&home).join(CLAUDE_DIR).join("CLAUDE.md");
+
+    let mut budgets = Vec::new();
+
+    for project_path in project_paths {
+        let project_name = PathBuf::from(&project_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Projet")
+            .to_string();
+
+        // Skills et commandes visibles dans ce projet (globaux + projet)
+        let skills = list_skills(vec![project_path.clone()]).await?;
+        let commands = list_commands(vec![project_path.clone()]).await?;
+
+        let mut session_start = BudgetSection::default();
+        let mut on_demand = BudgetSection::default();
+
+        // Fichiers mémoire chargés au démarrage
+        let root = PathBuf::from(&project_path);
+        let memory_files = [
+            ("CLAUDE.md global", global_memory.clone()),
+            ("CLAUDE.md", root.join("CLAUDE.md")),
+            (".claude/CLAUDE.md", root.join(CLAUDE_DIR).join("CLAUDE.md")),
+            ("CLAUDE.local.md", root.join("CLAUDE.local.md")),
+        ];
+        for (label, path) in memory_files {
+            if let Some(tokens) = file_tokens(&path) {
+                session_start.push(BudgetItem {
+                    kind: "memory".into(),
+                    label: label.into(),
+                    path: path.to_string_lossy().to_string(),
+                    tokens,
+                });
+            }
+        }
+
+        for skill in &skills {
+            session_start.push(BudgetItem {
+                kind: "skill_metadata".into(),
+                label: skill_label(skill),
+                path: skill.path.clone(),
+                tokens: estimate_tokens(&format!("{}: {}", skill.name, skill.description)),
+            });
+            on_demand.push(BudgetItem {
+                kind: "skill_body".into(),
+                label: skill_label(skill),
+                path: skill.path.clone(),
+                tokens: estimate_tokens(&skill.content),
+            });
+            for (path, tokens) in reference_files(Path::new(&skill.path)) {
+                on_demand.push(BudgetItem {
+                    kind: "reference".into(),
+                    label: format!("{} › {}", skill.name, path.file_name().unwrap_or_default().to_string_lossy()),
+                    path: path.to_string_lossy().to_string(),
+                    tokens,
+                });
+            }
+        }
+
+        for command in &commands {
+            if !command.description.is_empty() {
+                session_start.push(BudgetItem {
+                    kind: "command_metadata".into(),
+                    label: command.invocation.clone(),
+                    path: command.path.clone(),
+                    tokens: estimate_tokens(&format!("{}: {}", command.invocation, command.description)),
+                });
+            }
+            on_demand.push(BudgetItem {
+                kind: "command_body".into(),
+                label: command.invocation.clone(),
+                path: command.path.clone(),
+                tokens: estimate_tokens(&command.content),
+            });
+        }
+
+        session_start.items.sort_by_key(|item| std::cmp::Reverse(item.tokens));
+        on_demand.items.sort_by_key(|item| std::cmp::Reverse(item.tokens));
+
+        budgets.push(ProjectBudget {
+            project_name,
+            project_path,
+            context_window: CONTEXT_WINDOW_TOKENS,
+            session_start_percent: session_start.total_tokens as f64 * 100.0
+                / CONTEXT_WINDOW_TOKENS as f64,
+            session_start,
+            on_demand,
+        });
+    }
+
+    Ok(budgets)
+}
+
+fn skill_label(skill: &Skill) -> String {
+    match skill.source {
+        SkillSource::Global => format!("{} (global)", skill.name),
+        SkillSource::Project => skill.name.clone(),
+    }
+}
+
+fn file_tokens(path: &Path) -> Option<usize> {
+    fs::read_to_string(path).ok().map(|c| estimate_tokens(&c))
+}
+
+/// Fichiers texte de references/ avec leur estimation en tokens
+fn reference_files(skill_path: &Path) -> Vec<(PathBuf, usize)> {
+    let references_dir = skill_path.join("references");
+    if !references_dir.is_dir() {
+        return Vec::new();
+    }
+
+    WalkDir::new(&references_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| file_tokens(e.path()).map(|tokens| (e.path().to_path_buf(), tokens)))
+        .collect()
+}
@@ -0,0 +1,112 @@
+use crate::utils::hook_audit::{audit_command, risk_level, HookRisk, RiskLevel};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::settings::{load_hooks, HooksSource};
+use super::CLAUDE_DIR;
+
+/// Audit d'une commande de hook
+#[derive(Debug, Serialize)]
+pub struct HookAudit {
+    pub event: String,
+    pub matcher: Option<String>,
+    pub command: String,
+    pub risk_level: RiskLevel,
+    pub risks: Vec<HookRisk>,
+}
+
+/// Audit de tous les hooks d'une portée (settings.json global ou d'un projet)
+#[derive(Debug, Serialize)]
+pub struct HookScopeAudit {
+    pub label: String,
+    pub path: String,
+    pub risk_level: RiskLevel,
+    pub hooks: Vec<HookAudit>,
+}
+
+/// Évalue le risque de chaque commande de hook, par hook et par portée
+#[tauri::command]
+pub async fn audit_hooks(project_paths: Vec<String>) -> Result<Vec<HookScopeAudit>, String> {
+    let sources = load_hooks(project_paths).await?;
+    Ok(sources.iter().map(audit_source).collect())
+}
+
+fn audit_source(source: &HooksSource) -> HookScopeAudit {
+    let project_dir = project_dir_of(Path::new(&source.path));
+    let mut hooks = Vec::new();
+
+    // Structure : { "PreToolUse": [ { "matcher": "Bash", "hooks": [ { "type": "command", "command": "..." } ] } ] }
+    if let Some(events) = source.hooks.as_object() {
+        for (event, groups) in events {
+            for group in groups.as_array().into_iter().flatten() {
+                let matcher = group.get("matcher").and_then(Value::as_str).map(String::from);
+                for hook in group.get("hooks").and_then(Value::as_array).into_iter().flatten() {
+                    let Some(command) = hook.get("command").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let risks = audit_command(command, project_dir.as_deref());
+                    hooks.push(HookAudit {
+                        event: event.clone(),
+                        matcher: matcher.clone(),
+                        command: command.to_string(),
+                        risk_level: risk_level(&risks),
+                        risks,
+                    });
+                }
+            }
+        }
+    }
+
+    HookScopeAudit {
+        label: source.label.clone(),
+        path: source.path.clone(),
+        risk_level: hooks.iter().map(|h| h.risk_level).max().unwrap_or(RiskLevel::None),
+        hooks,
+    }
+}
+
+/// `<projet>/.claude/settings.json` → `<projet>` ; None pour les settings globaux
+fn project_dir_of(settings_path: &Path) -> Option<PathBuf> {
+    let claude_dir = settings_path.parent()?;
+    let project = claude_dir.parent()?;
+    let home = std::env::var("HOME").ok().map(PathBuf::from);
+
+    if claude_dir.file_name()?.to_str()? != CLAUDE_DIR || home.as_deref() == Some(project) {
+        return None;
+    }
+    Some(project.to_path_buf())
+}
+
+/// Rendu texte du rapport pour la ligne de commande
+pub fn format_report(audits: &[HookScopeAudit]) -> String {
+    let mut out = String::new();
+
+    for scope in audits {
+        out.push_str(&format!(
+            "{} [{}] ({})\n",
+            scope.label,
+            scope.risk_level.label(),
+            scope.path
+        ));
+        if scope.hooks.is_empty() {
+            out.push_str("  aucun hook\n");
+        }
+        for hook in &scope.hooks {
+            let matcher = hook.matcher.as_deref().map(|m| format!(" ({})", m)).unwrap_or_default();
+            out.push_str(&format!(
+                "  {:<8} {}{}: {}\n",
+                hook.risk_level.label(),
+                hook.event,
+                matcher,
+                hook.command
+            ));
+            for risk in &hook.risks {
+                out.push_str(&format!("           - {} {}: {}\n", risk.code, risk.message, risk.excerpt));
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
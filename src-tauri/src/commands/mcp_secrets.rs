@@ -0,0 +1,226 @@
+use crate::models::config::Config;
+use crate::utils::secret_scanner::scan_json;
+use crate::utils::vault::{read_env_file, read_vault, write_env_file, write_vault, SecretMap};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::security::mcp_blocks;
+use super::settings::{merge_and_write, read_json_file};
+
+/// Champs d'un serveur MCP dont les valeurs peuvent être externalisées
+const SECRET_FIELDS: [&str; 2] = ["env", "headers"];
+
+/// Valeur remplacée par une référence `${VAR}`
+#[derive(Debug, Serialize)]
+pub struct ExternalizedSecret {
+    pub block: String,      // Pointeur JSON du bloc mcpServers, ex. "/projects/~1work~1api/mcpServers"
+    pub server: String,
+    pub field: String,      // "env" | "headers"
+    pub key: String,
+    pub variable: String,
+}
+
+/// Environnement reconstitué pour lancer ou sonder les serveurs MCP
+#[derive(Debug, Serialize)]
+pub struct MaterializedEnv {
+    pub variables: SecretMap,
+    pub missing: Vec<String>,
+    /// Serveurs avec les références `${VAR}` remplacées par leurs valeurs
+    pub servers: Value,
+}
+
+fn env_reference_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").expect("env reference regex")
+    })
+}
+
+/// Remplace les secrets littéraux de `mcpServers.*.env` et `headers` par des références `${VAR}`
+///
+/// Tous les blocs signalés par `scan_secrets` sont traités, y compris `projects.*.mcpServers`
+/// de ~/.claude.json. `store` vaut "vault" (coffre chiffré, passphrase requise) ou "env"
+/// (fichier .env hors du dépôt).
+#[tauri::command]
+pub async fn externalize_mcp_secrets(
+    source_path: String,
+    store: String,
+    passphrase: Option<String>,
+) -> Result<Vec<ExternalizedSecret>, String> {
+    let path = PathBuf::from(&source_path);
+    let mut json = read_json_file(&path);
+    let blocks: Vec<String> = mcp_blocks(&json).into_iter().map(|(pointer, _)| pointer).collect();
+
+    let mut secrets = load_store(&store, passphrase.as_deref())?;
+    let mut replaced = Vec::new();
+    for block in &blocks {
+        if let Some(servers) = json.pointer_mut(block) {
+            externalize_block(block, servers, &mut secrets, &mut replaced);
+        }
+    }
+
+    if replaced.is_empty() {
+        return Ok(replaced);
+    }
+
+    // Les secrets sont stockés avant de réécrire la config : aucune valeur ne peut être perdue
+    save_store(&store, passphrase.as_deref(), &secrets)?;
+    for key in ["mcpServers", "projects"] {
+        if replaced.iter().any(|r| r.block.starts_with(&format!("/{}", key))) {
+            merge_and_write(&path, key, json[key].clone())?;
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    eprintln!("🔐 {} secret(s) MCP externalisé(s) depuis {}", replaced.len(), path.display());
+
+    Ok(replaced)
+}
+
+fn externalize_block(
+    block: &str,
+    servers: &mut Value,
+    secrets: &mut SecretMap,
+    replaced: &mut Vec<ExternalizedSecret>,
+) {
+    let Some(servers) = servers.as_object_mut() else {
+        return;
+    };
+    for (server, config) in servers.iter_mut() {
+        for field in SECRET_FIELDS {
+            let Some(values) = config.get_mut(field).and_then(|v| v.as_object_mut()) else {
+                continue;
+            };
+            for (key, value) in values.iter_mut() {
+                let Some(text) = value.as_str() else {
+                    continue;
+                };
+                // Analyse de la paire seule pour profiter de l'heuristique sur le nom de clé
+                let single = serde_json::json!({ key.as_str(): text });
+                let Some(found) = scan_json(&single).into_iter().next() else {
+                    continue;
+                };
+
+                let variable = variable_name(server, field, key, &found.value, secrets);
+                secrets.insert(variable.clone(), found.value.clone());
+                *value = Value::String(text.replacen(&found.value, &format!("${{{}}}", variable), 1));
+                replaced.push(ExternalizedSecret {
+                    block: block.to_string(),
+                    server: server.clone(),
+                    field: field.to_string(),
+                    key: key.clone(),
+                    variable,
+                });
+            }
+        }
+    }
+}
+
+/// Reconstitue l'environnement des serveurs MCP d'un fichier à partir du stockage local
+///
+/// Les variables absentes du stockage sont cherchées dans l'environnement du processus,
+/// puis dans la valeur par défaut `${VAR:-défaut}`.
+#[tauri::command]
+pub async fn materialize_mcp_env(
+    source_path: String,
+    store: String,
+    passphrase: Option<String>,
+) -> Result<MaterializedEnv, String> {
+    let servers = read_json_file(Path::new(&source_path))
+        .get("mcpServers")
+        .cloned()
+        .unwrap_or(Value::Object(serde_json::Map::new()));
+    let secrets = load_store(&store, passphrase.as_deref())?;
+
+    let mut variables = SecretMap::new();
+    let mut missing = Vec::new();
+    let servers = expand_references(&servers, &mut |name, default| {
+        let value = secrets
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+            .or_else(|| default.map(String::from));
+        match &value {
+            Some(v) => {
+                variables.insert(name.to_string(), v.clone());
+            }
+            None if !missing.iter().any(|m| m == name) => missing.push(name.to_string()),
+            None => {}
+        }
+        value
+    });
+
+    Ok(MaterializedEnv { variables, missing, servers })
+}
+
+fn expand_references(value: &Value, resolve: &mut dyn FnMut(&str, Option<&str>) -> Option<String>) -> Value {
+    match value {
+        Value::String(s) => Value::String(
+            env_reference_regex()
+                .replace_all(s, |caps: &regex::Captures| {
+                    resolve(&caps[1], caps.get(2).map(|m| m.as_str())).unwrap_or_else(|| caps[0].to_string())
+                })
+                .into_owned(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(|v| expand_references(v, resolve)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), expand_references(v, resolve)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Nom de variable : la clé d'env elle-même si libre, sinon préfixée par le serveur
+fn variable_name(server: &str, field: &str, key: &str, value: &str, secrets: &SecretMap) -> String {
+    let normalize = |s: &str| {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect::<String>()
+    };
+
+    let base = if field == "env" { normalize(key) } else { format!("{}_{}", normalize(server), normalize(key)) };
+    let candidates = std::iter::once(base.clone())
+        .chain(std::iter::once(format!("{}_{}", normalize(server), normalize(key))))
+        .chain((2..).map(|n| format!("{}_{}", base, n)));
+
+    for candidate in candidates {
+        match secrets.get(&candidate) {
+            None => return candidate,
+            Some(existing) if existing == value => return candidate,
+            Some(_) => continue,
+        }
+    }
+    unreachable!("suite de candidats infinie")
+}
+
+fn load_store(store: &str, passphrase: Option<&str>) -> Result<SecretMap, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    match store {
+        "vault" => {
+            let passphrase = passphrase.ok_or("Passphrase requise pour le coffre")?;
+            read_vault(Path::new(&config.expanded_vault_path()), passphrase)
+                .map_err(|e| format!("Erreur lecture coffre: {}", e))
+        }
+        "env" => read_env_file(Path::new(&config.expanded_env_file_path()))
+            .map_err(|e| format!("Erreur lecture fichier .env: {}", e)),
+        other => Err(format!("Stockage inconnu: {} (vault ou env)", other)),
+    }
+}
+
+fn save_store(store: &str, passphrase: Option<&str>, secrets: &SecretMap) -> Result<(), String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    match store {
+        "vault" => {
+            let passphrase = passphrase.ok_or("Passphrase requise pour le coffre")?;
+            write_vault(Path::new(&config.expanded_vault_path()), passphrase, secrets)
+                .map_err(|e| format!("Erreur écriture coffre: {}", e))
+        }
+        _ => write_env_file(Path::new(&config.expanded_env_file_path()), secrets)
+            .map_err(|e| format!("Erreur écriture fichier .env: {}", e)),
+    }
+}
@@ -1,11 +1,25 @@
 pub mod analysis;
+pub mod approvals;
+pub mod budget;
 pub mod config;
+pub mod hooks_audit;
+pub mod links;
+pub mod mcp_secrets;
+pub mod profiles;
 pub mod projects;
+pub mod routing;
+pub mod search;
+pub mod security;
 pub mod settings;
+pub mod skill_files;
 pub mod skills;
+pub mod slash_commands;
+pub mod transcripts;
+pub mod usage;
 
 // Constantes partagées pour les chemins
 pub const CLAUDE_DIR: &str = ".claude";
 pub const SKILLS_DIR: &str = "skills";
 pub const COMMANDS_DIR: &str = "commands";
+pub const AGENTS_DIR: &str = "agents";
 pub const MAX_SCAN_DEPTH: usize = 3;
@@ -1,14 +1,79 @@
 use crate::models::skill::Project;
 use crate::models::config::Config;
-use std::path::PathBuf;
-use walkdir::WalkDir;
+use crate::utils::approvals::ApprovalLedger;
+use crate::utils::claude_state::{discover_known_projects, KnownProject};
+use crate::utils::project_inventory::{forget_git_states, project_inventory};
+use crate::utils::project_scanner::{scan_base, unique_roots, ScanCache, ScanOptions};
+use crate::utils::project_stack::{detect_stack, render_claude_md, ProjectStack};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
 
-use super::{CLAUDE_DIR, SKILLS_DIR, MAX_SCAN_DEPTH};
+use super::approvals::{load_ledger_or_empty, needs_review};
+use super::{AGENTS_DIR, CLAUDE_DIR, COMMANDS_DIR, SKILLS_DIR, MAX_SCAN_DEPTH};
+
+/// Entrées ajoutées au .gitignore du projet (fichiers personnels)
+const GITIGNORE_ENTRIES: [&str; 2] = [".claude/settings.local.json", "CLAUDE.local.md"];
+
+/// Options d'initialisation d'un projet
+#[derive(Debug, Default, Deserialize)]
+pub struct InitOptions {
+    #[serde(default)]
+    pub mcp_json: bool,
+    #[serde(default)]
+    pub claude_md: bool,
+    /// Pré-autorise les commandes détectées (les scripts package.json exécutent du code du dépôt)
+    #[serde(default)]
+    pub allow_stack_commands: bool,
+}
+
+/// Résultat de l'initialisation : fichiers créés, fichiers existants conservés et pile détectée
+#[derive(Debug, Serialize)]
+pub struct InitReport {
+    pub project: Project,
+    pub stack: ProjectStack,
+    pub created: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Progression d'un parcours, émise dans l'événement "scan-progress"
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanProgress {
+    pub base_path: String,
+    pub scanned_dirs: usize,
+    pub found_projects: usize,
+    pub cached: bool,
+    pub done: bool,
+}
 
 /// Scanne les répertoires configurés pour trouver les projets avec .claude
+///
+/// Parcours parallèle respectant .gitignore et les motifs d'exclusion de la config, profondeur
+/// configurable par base. Une base dont aucun dossier n'a changé est relue depuis le cache,
+/// sauf avec `refresh`.
 #[tauri::command]
-pub async fn scan_projects(base_dirs: Vec<String>) -> Result<Vec<Project>, String> {
-    let mut projects = Vec::new();
+pub async fn scan_projects(
+    app: AppHandle,
+    base_dirs: Vec<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<Project>, String> {
+    tokio::task::spawn_blocking(move || scan_projects_blocking(&app, base_dirs, refresh.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Erreur scan des projets: {}", e))?
+}
+
+fn scan_projects_blocking(app: &AppHandle, base_dirs: Vec<String>, refresh: bool) -> Result<Vec<Project>, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    let cache_path = PathBuf::from(config.expanded_scan_cache_path());
+    let mut cache = ScanCache::load(&cache_path);
+    let ledger = load_ledger_or_empty();
+    let mut roots = Vec::new();
+    if refresh {
+        forget_git_states();
+    }
 
     // Scanner chaque répertoire de base
     for base_dir in base_dirs {
@@ -22,59 +87,50 @@ pub async fn scan_projects(base_dirs: Vec<String>) -> Result<Vec<Project>, Strin
             continue;
         }
 
+        let options = ScanOptions {
+            max_depth: config.scan_depth_for(&base_dir, MAX_SCAN_DEPTH),
+            excludes: config.scan_exclude_globs.clone(),
+        };
+        let emit = |scanned_dirs, found_projects, cached, done| {
+            let _ = app.emit(
+                "scan-progress",
+                ScanProgress { base_path: expanded_dir.clone(), scanned_dirs, found_projects, cached, done },
+            );
+        };
+
+        if let Some(cached) = cache.bases.get(&expanded_dir).filter(|c| !refresh && c.is_fresh(&options.fingerprint())) {
+            #[cfg(debug_assertions)]
+            eprintln!("♻️ Scan cache hit: {:?} ({} projects)", base_path, cached.roots.len());
+
+            emit(cached.dirs.len(), cached.roots.len(), true, true);
+            roots.extend(cached.roots.iter().cloned());
+            continue;
+        }
+
         #[cfg(debug_assertions)]
         eprintln!("🔍 Scanning for projects in: {:?}", base_path);
 
-        // Parcourir les sous-répertoires
-        for entry in WalkDir::new(&base_path)
-            .max_depth(MAX_SCAN_DEPTH)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-        let path = entry.path();
-
-        // Chercher les répertoires .claude
-        if path.ends_with(CLAUDE_DIR) && path.is_dir() {
-            if let Some(project_root) = path.parent() {
-                let project_name = project_root
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Unknown")
-                    .to_string();
-
-                let skills_path = path.join(SKILLS_DIR);
-
-                // Compter le nombre de fichiers .md dans le dossier skills (s'il existe)
-                let skill_count = if skills_path.exists() {
-                    std::fs::read_dir(&skills_path)
-                        .ok()
-                        .map(|entries| {
-                            entries
-                                .filter_map(|e| e.ok())
-                                .filter(|e| {
-                                    e.path()
-                                        .extension()
-                                        .and_then(|s| s.to_str())
-                                        == Some("md")
-                                })
-                                .count()
-                        })
-                        .unwrap_or(0)
-                } else {
-                    0
-                };
-
-                #[cfg(debug_assertions)]
-                eprintln!("✅ Found project: {} with {} skills", project_name, skill_count);
-
-                projects.push(Project {
-                    name: project_name,
-                    path: project_root.to_string_lossy().to_string(),
-                    skills_path: skills_path.to_string_lossy().to_string(),
-                    skill_count,
-                });
-            }
-        }
+        let scan = scan_base(&base_path, &options, &|scanned, found| emit(scanned, found, false, false))
+            .map_err(|e| format!("Erreur scan de {}: {}", base_dir, e))?;
+        emit(scan.dirs.len(), scan.roots.len(), false, true);
+        roots.extend(scan.roots.iter().cloned());
+        cache.bases.insert(expanded_dir, scan);
+    }
+
+    if let Err(e) = cache.save(&cache_path) {
+        #[cfg(debug_assertions)]
+        eprintln!("⚠️ Scan cache not saved: {}", e);
+    }
+
+    let mut projects: Vec<Project> = unique_roots(roots)
+        .iter()
+        .map(|root| project_at(root, &ledger))
+        .collect();
+
+    // Projets connus de Claude Code, même hors des dossiers de base ou sans dossier .claude
+    if config.discover_claude_projects {
+        if let Ok(home) = std::env::var("HOME") {
+            merge_known_projects(&mut projects, discover_known_projects(Path::new(&home)), &ledger);
         }
     }
 
@@ -83,3 +139,233 @@ pub async fn scan_projects(base_dirs: Vec<String>) -> Result<Vec<Project>, Strin
 
     Ok(projects)
 }
+
+/// Crée le dossier .claude d'un projet (skills, commands, agents, settings.json de départ),
+/// avec en option .mcp.json et un CLAUDE.md tiré de la pile détectée ; aucun fichier existant n'est écrasé
+#[tauri::command]
+pub async fn init_project(project_path: String, options: Option<InitOptions>) -> Result<InitReport, String> {
+    let options = options.unwrap_or_default();
+    let root = PathBuf::from(Config::expand_tilde(&project_path));
+    if !root.is_dir() {
+        return Err(format!("Dossier projet introuvable: {}", root.display()));
+    }
+
+    let stack = detect_stack(&root);
+    let claude_dir = root.join(CLAUDE_DIR);
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+    let relative = |path: &Path| path.strip_prefix(&root).unwrap_or(path).to_string_lossy().to_string();
+
+    for dir in [SKILLS_DIR, COMMANDS_DIR, AGENTS_DIR] {
+        let path = claude_dir.join(dir);
+        if path.exists() {
+            skipped.push(relative(&path));
+        } else {
+            fs::create_dir_all(&path).map_err(|e| format!("Erreur création dossier: {}", e))?;
+            created.push(relative(&path));
+        }
+    }
+
+    // Aucune autorisation par défaut ; sur demande, les commandes détectées sont autorisées telles
+    // quelles (règle exacte, sans préfixe `:*`). Les fichiers .env restent illisibles.
+    let allow: Vec<String> = if options.allow_stack_commands {
+        stack.commands.iter().map(|c| format!("Bash({})", c.command)).collect()
+    } else {
+        Vec::new()
+    };
+    let settings = json!({
+        "permissions": {
+            "allow": allow,
+            "deny": ["Read(./.env)", "Read(./.env.*)"],
+        }
+    });
+    let mut files = vec![(claude_dir.join("settings.json"), to_pretty_json(&settings)?)];
+    if options.mcp_json {
+        files.push((root.join(".mcp.json"), to_pretty_json(&json!({ "mcpServers": {} }))?));
+    }
+    if options.claude_md {
+        let name = root.file_name().and_then(|n| n.to_str()).unwrap_or("Project");
+        files.push((root.join("CLAUDE.md"), render_claude_md(name, &stack)));
+    }
+
+    for (path, content) in files {
+        if path.exists() {
+            skipped.push(relative(&path));
+        } else {
+            fs::write(&path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+            created.push(relative(&path));
+        }
+    }
+
+    if update_gitignore(&root.join(".gitignore"))? {
+        created.push(".gitignore".into());
+    }
+
+    #[cfg(debug_assertions)]
+    eprintln!("🚀 Projet initialisé: {} ({} élément(s) créé(s))", root.display(), created.len());
+
+    Ok(InitReport { project: project_at(&root, &load_ledger_or_empty()), stack, created, skipped })
+}
+
+fn to_pretty_json(value: &serde_json::Value) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| format!("Erreur sérialisation: {}", e))
+}
+
+/// Ajoute les entrées manquantes au .gitignore ; vrai si le fichier a été modifié
+fn update_gitignore(path: &Path) -> Result<bool, String> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let present: Vec<&str> = existing.lines().map(|l| l.trim().trim_start_matches('/')).collect();
+    let missing: Vec<&str> = GITIGNORE_ENTRIES.iter().copied().filter(|e| !present.contains(e)).collect();
+    if missing.is_empty() {
+        return Ok(false);
+    }
+
+    let mut content = existing;
+    if !content.is_empty() {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+    content.push_str("# Claude (fichiers personnels)\n");
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(path, content).map_err(|e| format!("Erreur écriture .gitignore: {}", e))?;
+    Ok(true)
+}
+
+fn merge_known_projects(projects: &mut Vec<Project>, known: Vec<KnownProject>, ledger: &ApprovalLedger) {
+    let key = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut index: HashMap<PathBuf, usize> = projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (key(Path::new(&p.path)), i))
+        .collect();
+
+    for entry in known {
+        let position = match index.get(&key(&entry.path)) {
+            Some(&position) => position,
+            None => {
+                index.insert(key(&entry.path), projects.len());
+                projects.push(project_at(&entry.path, ledger));
+                projects.len() - 1
+            }
+        };
+        let project = &mut projects[position];
+        project.last_used = project.last_used.max(entry.last_used);
+    }
+}
+
+/// Construit le projet situé à `project_root` (dossier .claude présent ou non)
+fn project_at(project_root: &Path, ledger: &ApprovalLedger) -> Project {
+    let project_name = project_root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+
+    let skills_path = project_root.join(CLAUDE_DIR).join(SKILLS_DIR);
+    let inventory = project_inventory(project_root);
+
+    Project {
+        name: project_name,
+        path: project_root.to_string_lossy().to_string(),
+        skills_path: skills_path.to_string_lossy().to_string(),
+        skill_count: inventory.own_skills + inventory.linked_skills,
+        inventory,
+        pending_review: needs_review(ledger, project_root),
+        missing: !project_root.is_dir(),
+        last_used: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("init-project-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        dir
+    }
+
+    fn init(project: &Path, options: InitOptions) -> InitReport {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime
+            .block_on(init_project(project.to_string_lossy().to_string(), Some(options)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_init_project_never_overwrites() {
+        let project = temp_project("keep");
+        fs::create_dir_all(project.join(".claude")).unwrap();
+        fs::write(project.join(".claude/settings.json"), "{\"model\": \"opus\"}").unwrap();
+        fs::write(project.join("CLAUDE.md"), "# Notes").unwrap();
+
+        let report = init(&project, InitOptions { mcp_json: true, claude_md: true, allow_stack_commands: true });
+        assert!(report.skipped.contains(&".claude/settings.json".to_string()));
+        assert!(report.skipped.contains(&"CLAUDE.md".to_string()));
+        assert!(report.created.contains(&".mcp.json".to_string()));
+        assert!(report.created.contains(&".claude/skills".to_string()));
+        assert_eq!(fs::read_to_string(project.join(".claude/settings.json")).unwrap(), "{\"model\": \"opus\"}");
+        assert_eq!(fs::read_to_string(project.join("CLAUDE.md")).unwrap(), "# Notes");
+
+        // Seconde initialisation : tout existe déjà
+        let again = init(&project, InitOptions::default());
+        assert!(again.created.is_empty());
+
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn test_init_project_allow_list_is_opt_in() {
+        let project = temp_project("allow");
+        init(&project, InitOptions::default());
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(project.join(".claude/settings.json")).unwrap()).unwrap();
+        assert_eq!(settings["permissions"]["allow"], json!([]));
+        assert_eq!(settings["permissions"]["deny"], json!(["Read(./.env)", "Read(./.env.*)"]));
+        assert!(!project.join(".mcp.json").exists());
+        assert!(!project.join("CLAUDE.md").exists());
+        fs::remove_dir_all(&project).unwrap();
+
+        let project = temp_project("allow-stack");
+        init(&project, InitOptions { allow_stack_commands: true, ..Default::default() });
+        let settings: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(project.join(".claude/settings.json")).unwrap()).unwrap();
+        let allow = settings["permissions"]["allow"].as_array().unwrap();
+        assert!(!allow.is_empty());
+        assert!(allow.iter().all(|rule| rule.as_str().unwrap().starts_with("Bash(cargo ")));
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn test_update_gitignore_appends_once() {
+        let project = temp_project("gitignore");
+        let path = project.join(".gitignore");
+
+        // Fichier sans retour à la ligne final, dont une entrée est déjà présente
+        fs::write(&path, "target\n/CLAUDE.local.md").unwrap();
+        assert!(update_gitignore(&path).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "target\n/CLAUDE.local.md\n\n# Claude (fichiers personnels)\n.claude/settings.local.json\n"
+        );
+        assert!(!update_gitignore(&path).unwrap());
+
+        // Fichier absent : créé avec les deux entrées
+        fs::remove_file(&path).unwrap();
+        assert!(update_gitignore(&path).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches(".claude/settings.local.json").count(), 1);
+        assert!(content.starts_with("# Claude") && content.ends_with("CLAUDE.local.md\n"));
+        assert!(!update_gitignore(&path).unwrap());
+
+        fs::remove_dir_all(&project).unwrap();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::models::skill::Skill;
+use crate::utils::text::{edit_distance, tokenize};
+use crate::utils::triggers::{cosine_similarity, extract_trigger_phrases, extract_use_when};
+use serde::{Deserialize, Serialize};
+
+use super::analysis::is_generic_description;
+use super::skills::list_skills;
+
+// Poids de chaque composante dans le score final
+const TRIGGER_WEIGHT: f64 = 0.45;
+const DESCRIPTION_WEIGHT: f64 = 0.30;
+const USE_WHEN_WEIGHT: f64 = 0.15;
+const NAME_WEIGHT: f64 = 0.10;
+
+/// Score (sur 100) à partir duquel un skill a de bonnes chances d'être chargé
+const LIKELY_THRESHOLD: f64 = 35.0;
+
+/// Détail d'une composante du score de routage
+#[derive(Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub label: String,   // "triggers" | "description" | "use_when" | "name"
+    pub weight: f64,
+    pub value: f64,      // 0.0 - 1.0
+    pub contribution: f64,
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RoutingMatch {
+    pub skill_id: String,
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+    pub score: f64,      // 0 - 100
+    pub likely: bool,
+    pub matched_triggers: Vec<String>,
+    pub components: Vec<ScoreComponent>,
+    pub notes: Vec<String>,
+}
+
+/// Simule le choix de skill pour un prompt : classe les skills visibles (globaux + projet)
+/// selon la proximité lexicale entre le prompt et leurs descriptions / trigger phrases
+#[tauri::command]
+pub async fn simulate_skill_routing(
+    prompt: String,
+    project_path: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<RoutingMatch>, String> {
+    let skills = list_skills(project_path.into_iter().collect()).await?;
+    Ok(rank_skills(&prompt, &skills, limit.unwrap_or(10)))
+}
+
+/// Skills ayant un score non nul pour le prompt, du plus au moins probable
+fn rank_skills(prompt: &str, skills: &[Skill], limit: usize) -> Vec<RoutingMatch> {
+    let prompt_tokens = tokenize(prompt);
+    if prompt_tokens.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<RoutingMatch> = skills
+        .iter()
+        .map(|skill| score_skill(prompt, &prompt_tokens, skill))
+        .filter(|m| m.score > 0.0)
+        .collect();
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(limit);
+    matches
+}
+
+fn score_skill(prompt: &str, prompt_tokens: &[String], skill: &Skill) -> RoutingMatch {
+    let triggers = extract_trigger_phrases(&skill.description);
+    let use_when = extract_use_when(&skill.description);
+    let mut notes = Vec::new();
+
+    // Trigger phrases : meilleure couverture d'une phrase par les tokens du prompt
+    let mut matched_triggers = Vec::new();
+    let mut trigger_value: f64 = 0.0;
+    for phrase in &triggers {
+        let coverage = token_coverage(&tokenize(phrase), prompt_tokens);
+        if coverage >= 0.5 {
+            matched_triggers.push(phrase.clone());
+        }
+        trigger_value = trigger_value.max(coverage);
+    }
+    let trigger_detail = if triggers.is_empty() {
+        notes.push("Aucune trigger phrase entre guillemets dans la description".to_string());
+        "aucune trigger phrase".to_string()
+    } else if matched_triggers.is_empty() {
+        format!("aucune des {} trigger phrases ne correspond", triggers.len())
+    } else {
+        format!("correspond à {}", matched_triggers.join(", "))
+    };
+
+    // Description complète
+    let description_value = cosine_similarity(prompt, &skill.description);
+    if skill.description.is_empty() {
+        notes.push("Description vide : Claude ne peut pas sélectionner ce skill".to_string());
+    } else if is_generic_description(&skill.description) {
+        notes.push("Description générique, peu discriminante".to_string());
+    }
+
+    // Clause « Use when »
+    let use_when_value = use_when
+        .as_deref()
+        .map(|clause| {
+            token_coverage(&tokenize(clause), prompt_tokens).max(cosine_similarity(prompt, clause))
+        })
+        .unwrap_or(0.0);
+    if use_when.is_none() {
+        notes.push("Pas de clause 'Use when...' dans la description".to_string());
+    }
+
+    // Nom du skill (kebab-case)
+    let name_value = token_coverage(&tokenize(&skill.name), prompt_tokens);
+
+    let components = vec![
+        component("triggers", TRIGGER_WEIGHT, trigger_value, trigger_detail),
+        component(
+            "description",
+            DESCRIPTION_WEIGHT,
+            description_value,
+            format!("similarité cosinus {:.2}", description_value),
+        ),
+        component(
+            "use_when",
+            USE_WHEN_WEIGHT,
+            use_when_value,
+            use_when.unwrap_or_else(|| "absente".to_string()),
+        ),
+        component("name", NAME_WEIGHT, name_value, skill.name.clone()),
+    ];
+
+    let score = components.iter().map(|c| c.contribution).sum::<f64>();
+
+    RoutingMatch {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        path: skill.path.clone(),
+        project_name: skill.project_name.clone(),
+        score,
+        likely: score >= LIKELY_THRESHOLD,
+        matched_triggers,
+        components,
+        notes,
+    }
+}
+
+fn component(label: &str, weight: f64, value: f64, detail: String) -> ScoreComponent {
+    ScoreComponent {
+        label: label.to_string(),
+        weight,
+        value,
+        contribution: weight * value * 100.0,
+        detail,
+    }
+}
+
+/// Part des tokens de `phrase` présents dans le prompt (tolère une faute de frappe)
+fn token_coverage(phrase: &[String], prompt_tokens: &[String]) -> f64 {
+    if phrase.is_empty() {
+        return 0.0;
+    }
+    let found = phrase
+        .iter()
+        .filter(|token| {
+            prompt_tokens.iter().any(|p| {
+                p == *token || (token.chars().count() >= 5 && edit_distance(p, token) <= 1)
+            })
+        })
+        .count();
+    found as f64 / phrase.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::skill::{SkillSource, TrustLevel};
+
+    fn skill(name: &str, description: &str) -> Skill {
+        let path = format!("/skills/{}/SKILL.md", name);
+        Skill {
+            id: Skill::generate_id(&path),
+            name: name.to_string(),
+            description: description.to_string(),
+            path,
+            source: SkillSource::Global,
+            project_name: None,
+            content: String::new(),
+            last_modified: 0,
+            linked_projects: Vec::new(),
+            trust_level: TrustLevel::Unverified,
+            usage_count: 0,
+            last_used: None,
+        }
+    }
+
+    fn skills() -> Vec<Skill> {
+        vec![
+            skill("pdf-export", "Exports documents to PDF. Use when the user wants a printable file."),
+            skill(
+                "docker-deploy",
+                "Deploys containers with docker compose. Use when the user asks to deploy the app. \
+                 Triggers: 'deploy app', 'ship to production'",
+            ),
+            skill("deploy-notes", "Writes release notes after a deploy."),
+        ]
+    }
+
+    #[test]
+    fn test_rank_skills_order() {
+        let ranked = rank_skills("please deploy app to production with docker", &skills(), 10);
+        let names: Vec<&str> = ranked.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["docker-deploy", "deploy-notes"]);
+
+        let best = &ranked[0];
+        assert!(best.likely);
+        assert_eq!(best.matched_triggers, vec!["deploy app", "ship to production"]);
+        assert!(best.score > ranked[1].score);
+        let total: f64 = best.components.iter().map(|c| c.contribution).sum();
+        assert!((total - best.score).abs() < 1e-9);
+
+        assert_eq!(rank_skills("please deploy app to production with docker", &skills(), 1).len(), 1);
+    }
+
+    #[test]
+    fn test_rank_skills_no_match() {
+        assert!(rank_skills("write a haiku about autumn", &skills(), 10).is_empty());
+        assert!(rank_skills("  ", &skills(), 10).is_empty());
+        assert!(rank_skills("deploy", &[], 10).is_empty());
+    }
+}
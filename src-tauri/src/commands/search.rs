@@ -0,0 +1,210 @@
+use crate::models::command::Command;
+use crate::models::config::Config;
+use crate::models::skill::{Skill, SkillSource};
+use crate::utils::file_watcher::start_watcher;
+use crate::utils::search_index::{DocumentKind, SearchDocument, SearchHit, SearchIndex};
+use notify::RecommendedWatcher;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use walkdir::WalkDir;
+
+use super::skills::{list_skills, load_skill, resolve_source};
+use super::slash_commands::{list_commands, load_command};
+use super::{CLAUDE_DIR, COMMANDS_DIR, SKILLS_DIR};
+
+/// Taille maximale lue par fichier de référence (les gros fichiers sont tronqués)
+const MAX_REFERENCE_BYTES: usize = 256 * 1024;
+
+/// État partagé : index de recherche et watcher qui le maintient à jour
+#[derive(Default)]
+pub struct SearchState {
+    pub index: Mutex<SearchIndex>,
+    pub watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+/// Reconstruit l'index (skills + commandes) et surveille les dossiers indexés
+#[tauri::command]
+pub async fn build_search_index(
+    app: AppHandle,
+    state: State<'_, SearchState>,
+    project_paths: Vec<String>,
+) -> Result<usize, String> {
+    let skills = list_skills(project_paths.clone()).await?;
+    let commands = list_commands(project_paths.clone()).await?;
+
+    let count = {
+        let mut index = state.index.lock().map_err(|e| e.to_string())?;
+        index.clear();
+        for skill in skills {
+            index.upsert(skill_document(skill));
+        }
+        for command in commands {
+            index.upsert(command_document(command));
+        }
+        index.len()
+    };
+
+    let watcher = start_watcher(watched_roots(&project_paths)?, app, |app, paths| {
+        reindex_paths(app, paths);
+    })
+    .map_err(|e| format!("Erreur démarrage watcher: {}", e))?;
+    *state.watcher.lock().map_err(|e| e.to_string())? = Some(watcher);
+
+    #[cfg(debug_assertions)]
+    eprintln!("🔎 Index de recherche construit: {} documents", count);
+
+    Ok(count)
+}
+
+/// Recherche plein texte (avec tolérance aux fautes) dans les skills et commandes indexés
+#[tauri::command]
+pub async fn search_skills(
+    state: State<'_, SearchState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    let index = state.index.lock().map_err(|e| e.to_string())?;
+    Ok(index.search(&query, limit.unwrap_or(20)))
+}
+
+fn skill_document(skill: Skill) -> SearchDocument {
+    let references = read_references(Path::new(&skill.path));
+    SearchDocument {
+        id: skill.id,
+        kind: DocumentKind::Skill,
+        path: skill.path,
+        source: skill.source,
+        project_name: skill.project_name,
+        name: skill.name,
+        description: skill.description,
+        body: skill.content,
+        references,
+    }
+}
+
+fn command_document(command: Command) -> SearchDocument {
+    SearchDocument {
+        id: command.id,
+        kind: DocumentKind::Command,
+        path: command.path,
+        source: command.source,
+        project_name: command.project_name,
+        name: command.name,
+        description: command.description,
+        body: command.content,
+        references: String::new(),
+    }
+}
+
+/// Concatène le contenu texte du dossier references/ d'un skill
+fn read_references(skill_path: &Path) -> String {
+    let references_dir = skill_path.join("references");
+    if !references_dir.is_dir() {
+        return String::new();
+    }
+
+    WalkDir::new(&references_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| fs::read(e.path()).ok())
+        .filter_map(|bytes| {
+            let bytes = &bytes[..bytes.len().min(MAX_REFERENCE_BYTES)];
+            std::str::from_utf8(bytes).ok().map(String::from)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Dossiers surveillés : skills et commandes globaux + ceux de chaque projet
+fn watched_roots(project_paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    let home = std::env::var("HOME").map_err(|_| "Variable HOME non définie".to_string())?;
+
+    let mut roots = vec![
+        PathBuf::from(config.expanded_global_skills_path()),
+        PathBuf::from(home).join(CLAUDE_DIR).join(COMMANDS_DIR),
+    ];
+    for project_path in project_paths {
+        let claude_dir = PathBuf::from(project_path).join(CLAUDE_DIR);
+        roots.push(claude_dir.join(SKILLS_DIR));
+        roots.push(claude_dir.join(COMMANDS_DIR));
+    }
+
+    Ok(roots)
+}
+
+/// Met à jour l'index pour les fichiers modifiés signalés par le watcher
+fn reindex_paths(app: &AppHandle, paths: &[PathBuf]) {
+    let state = app.state::<SearchState>();
+    let Ok(mut index) = state.index.lock() else {
+        return;
+    };
+
+    for changed in paths {
+        // Documents existants concernés : le dossier du skill ou le fichier de commande
+        let affected: Vec<SearchDocument> = index
+            .documents()
+            .filter(|d| changed.starts_with(&d.path))
+            .cloned()
+            .collect();
+
+        if affected.is_empty() {
+            if let Some(document) = load_new_document(changed) {
+                index.upsert(document);
+            }
+            continue;
+        }
+
+        for document in affected {
+            let path = PathBuf::from(&document.path);
+            let reloaded = match document.kind {
+                DocumentKind::Skill => load_skill(&path, document.source, document.project_name)
+                    .ok()
+                    .map(skill_document),
+                DocumentKind::Command => load_command(&path, document.source, document.project_name)
+                    .ok()
+                    .map(command_document),
+            };
+            match reloaded {
+                Some(updated) => index.upsert(updated),
+                None => {
+                    index.remove(&document.id);
+                }
+            }
+        }
+    }
+}
+
+/// Charge un document pour un fichier qui n'était pas encore indexé (nouveau skill ou commande)
+fn load_new_document(changed: &Path) -> Option<SearchDocument> {
+    if changed.file_name().and_then(|n| n.to_str()) == Some("SKILL.md") {
+        let folder = changed.parent()?;
+        let (source, project_name) = global_or_project(folder, SKILLS_DIR)?;
+        return load_skill(folder, source, project_name).ok().map(skill_document);
+    }
+
+    let in_commands_dir = changed
+        .ancestors()
+        .any(|p| p.file_name().and_then(|n| n.to_str()) == Some(COMMANDS_DIR));
+    if in_commands_dir && changed.extension().and_then(|s| s.to_str()) == Some("md") {
+        let (source, project_name) = global_or_project(changed, COMMANDS_DIR)?;
+        return load_command(changed, source, project_name).ok().map(command_document);
+    }
+
+    None
+}
+
+fn global_or_project(path: &Path, subdir: &str) -> Option<(SkillSource, Option<String>)> {
+    // Le dossier des skills globaux est configurable
+    if subdir == SKILLS_DIR {
+        if let Ok(config) = Config::load() {
+            if path.starts_with(config.expanded_global_skills_path()) {
+                return Some((SkillSource::Global, None));
+            }
+        }
+    }
+    resolve_source(path, subdir).ok()
+}
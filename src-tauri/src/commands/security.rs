@@ -0,0 +1,207 @@
+use crate::models::config::Config;
+use crate::utils::paths::looks_binary;
+use crate::utils::secret_scanner::{scan_json, scan_text, SecretMatch};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{CLAUDE_DIR, COMMANDS_DIR, SKILLS_DIR};
+
+/// Taille maximale d'un fichier analysé
+const MAX_SCAN_BYTES: u64 = 1024 * 1024;
+
+/// Sélection des sous-documents JSON à analyser (préfixe de pointeur, valeur)
+type BlockSelector = fn(&Value) -> Vec<(String, &Value)>;
+
+/// Secret détecté, avec son emplacement
+#[derive(Debug, Serialize)]
+pub struct SecretFinding {
+    pub rule: String,
+    pub description: String,
+    pub scope: String,            // "skill" | "command" | "settings" | "mcp"
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_pointer: Option<String>,
+    pub redacted: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SecretReport {
+    pub scanned_files: usize,
+    pub findings: Vec<SecretFinding>,
+}
+
+/// Recherche les secrets dans les skills, commandes, settings (toutes portées) et configurations MCP
+#[tauri::command]
+pub async fn scan_secrets(project_paths: Vec<String>) -> Result<SecretReport, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    let home = std::env::var("HOME").map_err(|_| "Variable HOME non définie".to_string())?;
+    let claude_dir = PathBuf::from(&home).join(CLAUDE_DIR);
+
+    let mut report = SecretReport { scanned_files: 0, findings: Vec::new() };
+
+    // Portée utilisateur
+    scan_tree(&PathBuf::from(config.expanded_global_skills_path()), "skill", &mut report);
+    scan_tree(&claude_dir.join(COMMANDS_DIR), "command", &mut report);
+    for name in ["settings.json", "settings.local.json"] {
+        scan_json_file(&claude_dir.join(name), "settings", None, &mut report);
+    }
+    // ~/.claude.json contient aussi l'état de l'application : seuls les blocs MCP sont analysés
+    scan_json_file(&PathBuf::from(&home).join(".claude.json"), "mcp", Some(mcp_blocks), &mut report);
+
+    // Portée projet
+    for project_path in project_paths {
+        let project = PathBuf::from(&project_path);
+        let project_claude = project.join(CLAUDE_DIR);
+        scan_tree(&project_claude.join(SKILLS_DIR), "skill", &mut report);
+        scan_tree(&project_claude.join(COMMANDS_DIR), "command", &mut report);
+        for name in ["settings.json", "settings.local.json"] {
+            scan_json_file(&project_claude.join(name), "settings", None, &mut report);
+        }
+        scan_json_file(&project.join(".mcp.json"), "mcp", None, &mut report);
+    }
+
+    Ok(report)
+}
+
+/// Secrets contenus dans un fichier ou un dossier (skill ou commande)
+pub fn secrets_in_path(path: &Path) -> Vec<SecretFinding> {
+    let mut report = SecretReport { scanned_files: 0, findings: Vec::new() };
+    scan_tree(path, "skill", &mut report);
+    report.findings
+}
+
+/// Refuse de lier ou copier un skill/une commande contenant des secrets dans un dépôt git
+pub fn ensure_no_secrets_for_repo(source: &Path, target_project: &Path) -> Result<(), String> {
+    if !is_in_git_repo(target_project) {
+        return Ok(());
+    }
+
+    let findings = secrets_in_path(source);
+    let Some(first) = findings.first() else {
+        return Ok(());
+    };
+
+    let location = match first.line {
+        Some(line) => format!("{}:{}", first.path, line),
+        None => first.path.clone(),
+    };
+    Err(format!(
+        "Opération bloquée : {} secret(s) détecté(s) et la destination est un dépôt git ({} dans {})",
+        findings.len(),
+        first.description,
+        location
+    ))
+}
+
+fn is_in_git_repo(path: &Path) -> bool {
+    path.ancestors().any(|p| p.join(".git").exists())
+}
+
+/// Blocs `mcpServers` de ~/.claude.json (global et par projet), avec leur pointeur JSON
+pub fn mcp_blocks(json: &Value) -> Vec<(String, &Value)> {
+    let mut blocks = Vec::new();
+    if let Some(servers) = json.get("mcpServers") {
+        blocks.push(("/mcpServers".to_string(), servers));
+    }
+    if let Some(projects) = json.get("projects").and_then(|p| p.as_object()) {
+        for (project, config) in projects {
+            if let Some(servers) = config.get("mcpServers") {
+                let key = project.replace('~', "~0").replace('/', "~1");
+                blocks.push((format!("/projects/{}/mcpServers", key), servers));
+            }
+        }
+    }
+    blocks
+}
+
+fn scan_tree(root: &Path, scope: &str, report: &mut SecretReport) {
+    if !root.exists() {
+        return;
+    }
+
+    for entry in WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            scan_json_file(path, scope, None, report);
+        } else {
+            scan_text_file(path, scope, report);
+        }
+    }
+}
+
+fn read_scannable(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > MAX_SCAN_BYTES {
+        return None;
+    }
+    let data = fs::read(path).ok()?;
+    if looks_binary(&data) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&data).into_owned())
+}
+
+fn scan_text_file(path: &Path, scope: &str, report: &mut SecretReport) {
+    let Some(content) = read_scannable(path) else {
+        return;
+    };
+    report.scanned_files += 1;
+    for found in scan_text(&content) {
+        report.findings.push(finding(found, scope, path));
+    }
+}
+
+fn scan_json_file(
+    path: &Path,
+    scope: &str,
+    select: Option<BlockSelector>,
+    report: &mut SecretReport,
+) {
+    let Some(content) = read_scannable(path) else {
+        return;
+    };
+    let Ok(json) = serde_json::from_str::<Value>(&content) else {
+        // JSON invalide : analyse comme texte
+        scan_text_file(path, scope, report);
+        return;
+    };
+    report.scanned_files += 1;
+
+    let blocks = match select {
+        Some(select) => select(&json),
+        None => vec![(String::new(), &json)],
+    };
+    for (prefix, value) in blocks {
+        for mut found in scan_json(value) {
+            found.json_pointer = found.json_pointer.map(|p| format!("{}{}", prefix, p));
+            // Ligne approximative : première occurrence de la valeur dans le fichier
+            found.line = content
+                .lines()
+                .position(|l| l.contains(&found.value))
+                .map(|i| i + 1);
+            report.findings.push(finding(found, scope, path));
+        }
+    }
+}
+
+fn finding(found: SecretMatch, scope: &str, path: &Path) -> SecretFinding {
+    SecretFinding {
+        rule: found.rule.to_string(),
+        description: found.description.to_string(),
+        scope: scope.to_string(),
+        path: path.to_string_lossy().to_string(),
+        line: found.line,
+        json_pointer: found.json_pointer.clone(),
+        redacted: found.redacted(),
+    }
+}
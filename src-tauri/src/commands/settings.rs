@@ -1,6 +1,6 @@
 use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::CLAUDE_DIR;
 
@@ -9,7 +9,7 @@ fn claude_json_path() -> Result<PathBuf, String> {
     Ok(PathBuf::from(home).join(".claude.json"))
 }
 
-fn read_json_file(path: &PathBuf) -> serde_json::Value {
+pub fn read_json_file(path: &Path) -> serde_json::Value {
     if !path.exists() {
         return serde_json::Value::Object(serde_json::Map::new());
     }
@@ -19,7 +19,7 @@ fn read_json_file(path: &PathBuf) -> serde_json::Value {
         .unwrap_or(serde_json::Value::Object(serde_json::Map::new()))
 }
 
-fn merge_and_write(path: &PathBuf, key: &str, value: serde_json::Value) -> Result<(), String> {
+pub fn merge_and_write(path: &Path, key: &str, value: serde_json::Value) -> Result<(), String> {
     let mut json = read_json_file(path);
     if let Some(obj) = json.as_object_mut() {
         obj.insert(key.to_string(), value);
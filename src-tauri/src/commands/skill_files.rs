@@ -0,0 +1,299 @@
+use crate::utils::paths::{ensure_structure_path, looks_binary, relative_path, safe_join};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Taille maximale d'un fichier ouvert dans l'éditeur
+const MAX_EDITABLE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Noeud de l'arborescence d'un skill
+#[derive(Debug, Serialize)]
+pub struct SkillFileNode {
+    pub name: String,
+    pub relative_path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub binary: bool,
+    pub executable: bool,
+    pub children: Vec<SkillFileNode>,
+}
+
+/// Contenu d'un fichier du skill (None si binaire ou trop volumineux)
+#[derive(Debug, Serialize)]
+pub struct SkillFileContent {
+    pub relative_path: String,
+    pub size: u64,
+    pub binary: bool,
+    pub executable: bool,
+    pub content: Option<String>,
+}
+
+/// Arborescence complète du dossier d'un skill
+#[tauri::command]
+pub async fn list_skill_files(skill_path: String) -> Result<Vec<SkillFileNode>, String> {
+    let root = fs::canonicalize(&skill_path).map_err(|e| format!("Erreur lecture dossier: {}", e))?;
+    if !root.is_dir() {
+        return Err("Le dossier du skill n'existe pas".to_string());
+    }
+
+    read_tree(&root, &root)
+}
+
+/// Lit un fichier du skill (SKILL.md compris)
+#[tauri::command]
+pub async fn read_skill_file(skill_path: String, relative_path: String) -> Result<SkillFileContent, String> {
+    let file_path = safe_join(Path::new(&skill_path), &relative_path)?;
+    let metadata = fs::metadata(&file_path).map_err(|e| format!("Erreur lecture fichier: {}", e))?;
+    if metadata.is_dir() {
+        return Err("Le chemin désigne un dossier".to_string());
+    }
+
+    let size = metadata.len();
+    let (binary, content) = if size > MAX_EDITABLE_BYTES {
+        (false, None)
+    } else {
+        let data = fs::read(&file_path).map_err(|e| format!("Erreur lecture fichier: {}", e))?;
+        if looks_binary(&data) {
+            (true, None)
+        } else {
+            (false, Some(String::from_utf8_lossy(&data).into_owned()))
+        }
+    };
+
+    Ok(SkillFileContent {
+        relative_path,
+        size,
+        binary,
+        executable: is_executable(&metadata),
+        content,
+    })
+}
+
+/// Crée ou remplace un fichier texte sous scripts/, references/ ou assets/
+#[tauri::command]
+pub async fn write_skill_file(skill_path: String, relative_path: String, content: String) -> Result<(), String> {
+    write_structure_file(&skill_path, &relative_path, content.as_bytes())
+}
+
+/// Crée ou remplace un fichier binaire (image, archive...) sous scripts/, references/ ou assets/
+#[tauri::command]
+pub async fn write_skill_file_bytes(
+    skill_path: String,
+    relative_path: String,
+    content: Vec<u8>,
+) -> Result<(), String> {
+    write_structure_file(&skill_path, &relative_path, &content)
+}
+
+fn write_structure_file(skill_path: &str, relative_path: &str, content: &[u8]) -> Result<(), String> {
+    ensure_structure_path(relative_path)?;
+    let file_path = safe_join(Path::new(skill_path), relative_path)?;
+    if file_path.is_dir() {
+        return Err("Le chemin désigne un dossier".to_string());
+    }
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier: {}", e))?;
+    }
+    fs::write(&file_path, content).map_err(|e| format!("Erreur écriture fichier: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("💾 Fichier du skill écrit: {}", file_path.display());
+
+    Ok(())
+}
+
+/// Renomme ou déplace un fichier (ou dossier) entre scripts/, references/ et assets/
+#[tauri::command]
+pub async fn rename_skill_file(skill_path: String, from: String, to: String) -> Result<(), String> {
+    ensure_structure_path(&from)?;
+    ensure_structure_path(&to)?;
+    let source = safe_join(Path::new(&skill_path), &from)?;
+    let destination = safe_join(Path::new(&skill_path), &to)?;
+
+    if fs::symlink_metadata(&source).is_err() {
+        return Err(format!("Le fichier {} n'existe pas", from));
+    }
+    if fs::symlink_metadata(&destination).is_ok() {
+        return Err(format!("Le fichier {} existe déjà", to));
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier: {}", e))?;
+    }
+    fs::rename(&source, &destination).map_err(|e| format!("Erreur renommage: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("✏️ Fichier du skill renommé: {} -> {}", from, to);
+
+    Ok(())
+}
+
+/// Supprime un fichier ou un sous-dossier sous scripts/, references/ ou assets/
+#[tauri::command]
+pub async fn delete_skill_file(skill_path: String, relative_path: String) -> Result<(), String> {
+    ensure_structure_path(&relative_path)?;
+    let file_path = safe_join(Path::new(&skill_path), &relative_path)?;
+
+    let metadata = fs::symlink_metadata(&file_path)
+        .map_err(|_| format!("Le fichier {} n'existe pas", relative_path))?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(&file_path).map_err(|e| format!("Erreur suppression dossier: {}", e))?;
+    } else {
+        fs::remove_file(&file_path).map_err(|e| format!("Erreur suppression fichier: {}", e))?;
+    }
+
+    #[cfg(debug_assertions)]
+    eprintln!("🗑️ Fichier du skill supprimé: {}", file_path.display());
+
+    Ok(())
+}
+
+/// Active ou retire le bit exécutable d'un script (scripts/ uniquement)
+#[tauri::command]
+pub async fn set_skill_file_executable(
+    skill_path: String,
+    relative_path: String,
+    executable: bool,
+) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    ensure_structure_path(&relative_path)?;
+    let file_path = safe_join(Path::new(&skill_path), &relative_path)?;
+
+    // Chemin résolu (`./`, liens compris) : il doit rester sous scripts/
+    let scripts = fs::canonicalize(Path::new(&skill_path).join("scripts"))
+        .map_err(|_| "Seuls les fichiers de scripts/ peuvent être exécutables".to_string())?;
+    let resolved = fs::canonicalize(&file_path).map_err(|e| format!("Erreur lecture fichier: {}", e))?;
+    if !resolved.starts_with(&scripts) {
+        return Err("Seuls les fichiers de scripts/ peuvent être exécutables".to_string());
+    }
+
+    let mut permissions = fs::metadata(&resolved)
+        .map_err(|e| format!("Erreur lecture fichier: {}", e))?
+        .permissions();
+    let mode = permissions.mode();
+    permissions.set_mode(if executable { mode | 0o111 } else { mode & !0o111 });
+    fs::set_permissions(&resolved, permissions).map_err(|e| format!("Erreur changement permissions: {}", e))
+}
+
+fn read_tree(root: &Path, dir: &Path) -> Result<Vec<SkillFileNode>, String> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Erreur lecture dossier: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+
+    let mut nodes = Vec::new();
+    for path in entries {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue; // symlink cassé
+        };
+        let is_dir = metadata.is_dir();
+
+        // Les dossiers symlinkés ne sont pas parcourus (boucles possibles)
+        let is_symlink = fs::symlink_metadata(&path).map(|m| m.is_symlink()).unwrap_or(false);
+        let children = if is_dir && !is_symlink {
+            read_tree(root, &path)?
+        } else {
+            Vec::new()
+        };
+
+        nodes.push(SkillFileNode {
+            name: path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string(),
+            relative_path: relative_path(root, &path),
+            is_dir,
+            size: if is_dir { 0 } else { metadata.len() },
+            binary: !is_dir && file_is_binary(&path),
+            executable: !is_dir && is_executable(&metadata),
+            children,
+        });
+    }
+
+    Ok(nodes)
+}
+
+fn file_is_binary(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut head = Vec::with_capacity(8192);
+    fs::File::open(path)
+        .and_then(|f| f.take(8192).read_to_end(&mut head))
+        .map(|_| looks_binary(&head))
+        .unwrap_or(false)
+}
+
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn skill_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skill-files-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("references")).unwrap();
+        fs::write(dir.join("SKILL.md"), "# Skill").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_rejects_paths_outside_structure() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dir = skill_dir("write");
+        let skill = dir.to_string_lossy().to_string();
+        let write = |relative: &str| {
+            runtime.block_on(write_skill_file(skill.clone(), relative.to_string(), "x".to_string()))
+        };
+
+        assert!(write("SKILL.md").is_err());
+        assert!(write("scripts/../../escape.sh").is_err());
+        assert!(write("/tmp/escape.sh").is_err());
+        std::os::unix::fs::symlink(std::env::temp_dir(), dir.join("assets")).unwrap();
+        assert!(write("assets/escape.txt").is_err());
+        assert!(!std::env::temp_dir().join("escape.txt").exists());
+
+        write("scripts/run.sh").unwrap();
+        assert_eq!(fs::read_to_string(dir.join("scripts/run.sh")).unwrap(), "x");
+        let png = vec![0x89, b'P', b'N', b'G', 0, 0xff];
+        runtime
+            .block_on(write_skill_file_bytes(skill.clone(), "references/logo.png".into(), png.clone()))
+            .unwrap();
+        assert_eq!(fs::read(dir.join("references/logo.png")).unwrap(), png);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_executable_bit_only_under_scripts() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dir = skill_dir("exec");
+        let skill = dir.to_string_lossy().to_string();
+        let set = |relative: &str, executable: bool| {
+            runtime.block_on(set_skill_file_executable(skill.clone(), relative.to_string(), executable))
+        };
+        let mode = |relative: &str| fs::metadata(dir.join(relative)).unwrap().permissions().mode() & 0o111;
+
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::write(dir.join("references/notes.md"), "notes").unwrap();
+
+        set("scripts/run.sh", true).unwrap();
+        assert_eq!(mode("scripts/run.sh"), 0o111);
+        set("./scripts/run.sh", false).unwrap();
+        assert_eq!(mode("scripts/run.sh"), 0);
+
+        assert!(set("references/notes.md", true).is_err());
+        // Un lien sous scripts/ vers references/ ne contourne pas la restriction
+        std::os::unix::fs::symlink(dir.join("references/notes.md"), dir.join("scripts/notes")).unwrap();
+        assert!(set("scripts/notes", true).is_err());
+        assert_eq!(mode("references/notes.md"), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
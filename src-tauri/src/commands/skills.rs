@@ -1,68 +1,62 @@
-use crate::models::skill::{Skill, SkillSource};
+use crate::models::skill::{Skill, SkillProvenance, SkillSource};
 use crate::models::config::Config;
+use crate::utils::injection_scanner::{scan_skill_cached, trust_level};
 use crate::utils::markdown_parser::parse_frontmatter;
+use crate::utils::symlinks::{create_symlink, LinkStyle};
+use crate::utils::skill_templates::{
+    builtin_templates, load_user_templates, scaffold_skill, SkillTemplate, TemplateVars,
+    STRUCTURE_FOLDERS,
+};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use super::{CLAUDE_DIR, COMMANDS_DIR, SKILLS_DIR};
+use super::analysis::detect_category;
+use super::links::link_map;
+use super::security::ensure_no_secrets_for_repo;
+use super::{CLAUDE_DIR, SKILLS_DIR};
 
-/// Liste tous les skills (globaux + projets)
+/// Liste tous les skills (globaux + projets) ; l'utilisation est chargée à part (`skill_usage`)
 #[tauri::command]
 pub async fn list_skills(project_paths: Vec<String>) -> Result<Vec<Skill>, String> {
-    use std::collections::HashMap;
-
     let mut skills = Vec::new();
-    let mut symlink_map: HashMap<String, Vec<String>> = HashMap::new();
+    let symlink_map = link_map(&project_paths, SKILLS_DIR);
 
     // 1. Scanner les skills globaux (depuis la config)
     let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
     let global_path = PathBuf::from(config.expanded_global_skills_path());
+    let provenance = load_provenance(&config);
 
-    if let Ok(global_skills) = scan_skills_directory(&global_path, SkillSource::Global, None) {
+    if let Ok(global_skills) = scan_skills_directory(&global_path, SkillSource::Global, None, &provenance) {
         skills.extend(global_skills);
     }
 
     // 2. Scanner les skills des projets fournis
-    for project_path in project_paths {
-        let project_skills_path = PathBuf::from(&project_path)
+    for project_path in &project_paths {
+        let project_skills_path = PathBuf::from(project_path)
             .join(CLAUDE_DIR)
             .join(SKILLS_DIR);
         if project_skills_path.exists() {
-            let project_name = PathBuf::from(&project_path)
+            let project_name = PathBuf::from(project_path)
                 .file_name()
                 .and_then(|n| n.to_str())
                 .map(String::from);
 
             if let Ok(proj_skills) =
-                scan_skills_directory(&project_skills_path, SkillSource::Project, project_name.clone())
+                scan_skills_directory(&project_skills_path, SkillSource::Project, project_name, &provenance)
             {
-                // Vérifier les symlinks (dossiers) et construire la map
-                for skill in &proj_skills {
-                    let skill_path = PathBuf::from(&skill.path);
-                    if let Ok(metadata) = fs::symlink_metadata(&skill_path) {
-                        if metadata.is_symlink() {
-                            if let Ok(target) = fs::read_link(&skill_path) {
-                                let target_str = target.to_string_lossy().to_string();
-                                if let Some(ref pname) = project_name {
-                                    symlink_map
-                                        .entry(target_str)
-                                        .or_insert_with(Vec::new)
-                                        .push(pname.clone());
-                                }
-                            }
-                        }
-                    }
-                }
-
                 skills.extend(proj_skills);
             }
         }
     }
 
-    // 3. Mettre à jour linked_projects pour chaque skill
+    // 3. Mettre à jour linked_projects en comparant les chemins canoniques
     for skill in &mut skills {
-        if let Some(projects) = symlink_map.get(&skill.path) {
+        let is_symlink = fs::symlink_metadata(&skill.path).map(|m| m.is_symlink()).unwrap_or(false);
+        if is_symlink {
+            continue;
+        }
+        if let Some(projects) = fs::canonicalize(&skill.path).ok().and_then(|p| symlink_map.get(&p)) {
             skill.linked_projects = projects.clone();
         }
     }
@@ -70,99 +64,32 @@ pub async fn list_skills(project_paths: Vec<String>) -> Result<Vec<Skill>, Strin
     Ok(skills)
 }
 
-/// Liste toutes les commandes (globales + projets)
-#[tauri::command]
-pub async fn list_commands(project_paths: Vec<String>) -> Result<Vec<Skill>, String> {
-    let mut commands = Vec::new();
-
-    let home = std::env::var("HOME").map_err(|_| "Variable HOME non définie".to_string())?;
-    let global_commands_path = PathBuf::from(home).join(CLAUDE_DIR).join(COMMANDS_DIR);
-
-    if let Ok(global_cmds) = scan_commands_directory(&global_commands_path, SkillSource::Global, None) {
-        commands.extend(global_cmds);
-    }
-
-    for project_path in project_paths {
-        let project_cmds_path = PathBuf::from(&project_path)
-            .join(CLAUDE_DIR)
-            .join(COMMANDS_DIR);
-        if project_cmds_path.exists() {
-            let project_name = PathBuf::from(&project_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .map(String::from);
-            if let Ok(cmds) = scan_commands_directory(&project_cmds_path, SkillSource::Project, project_name) {
-                commands.extend(cmds);
-            }
-        }
-    }
-
-    Ok(commands)
-}
-
-fn scan_commands_directory(
-    base_path: &Path,
-    source: SkillSource,
-    project_name: Option<String>,
-) -> Result<Vec<Skill>, String> {
-    let mut commands = Vec::new();
-
-    if !base_path.exists() {
-        return Ok(commands);
-    }
-
-    for entry in WalkDir::new(base_path)
-        .max_depth(2)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-            let filename_name = path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("Untitled")
-                .to_string();
-
-            match load_command(path, source.clone(), project_name.clone()) {
-                Ok(mut skill) => {
-                    if skill.name == "Untitled Skill" || skill.name.is_empty() {
-                        skill.name = filename_name;
-                    }
-                    commands.push(skill);
-                }
-                Err(e) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("Error loading command {}: {}", path.display(), e);
-                }
-            }
-        }
-    }
-
-    Ok(commands)
-}
-
 /// Récupère un skill spécifique par son chemin (chemin du dossier)
 #[tauri::command]
 pub async fn get_skill(path: String) -> Result<Skill, String> {
     let folder_path = PathBuf::from(&path);
+    let (source, project_name) = resolve_source(&folder_path, SKILLS_DIR)?;
 
+    load_skill(&folder_path, source, project_name).map_err(|e| e.to_string())
+}
+
+/// Détermine la source (globale ou projet) d'un chemin situé sous ~/.claude/<subdir>
+/// ou sous <projet>/.claude/<subdir>
+pub fn resolve_source(path: &Path, subdir: &str) -> Result<(SkillSource, Option<String>), String> {
     let home = std::env::var("HOME").map_err(|_| "HOME env var not set")?;
-    let global_skills_path = PathBuf::from(&home).join(CLAUDE_DIR).join(SKILLS_DIR);
+    let global_path = PathBuf::from(&home).join(CLAUDE_DIR).join(subdir);
 
-    let (source, project_name) = if folder_path.starts_with(&global_skills_path) {
-        (SkillSource::Global, None)
+    if path.starts_with(&global_path) {
+        Ok((SkillSource::Global, None))
     } else {
-        let project_name = folder_path
+        let project_name = path
             .ancestors()
-            .find(|p| p.join(CLAUDE_DIR).join(SKILLS_DIR).exists())
+            .find(|p| p.join(CLAUDE_DIR).join(subdir).exists())
             .and_then(|p| p.file_name())
             .and_then(|n| n.to_str())
             .map(String::from);
-        (SkillSource::Project, project_name)
-    };
-
-    load_skill(&folder_path, source, project_name).map_err(|e| e.to_string())
+        Ok((SkillSource::Project, project_name))
+    }
 }
 
 /// Scanne un répertoire pour trouver tous les dossiers contenant SKILL.md
@@ -170,6 +97,7 @@ fn scan_skills_directory(
     base_path: &Path,
     source: SkillSource,
     project_name: Option<String>,
+    provenance: &SkillProvenance,
 ) -> Result<Vec<Skill>, String> {
     let mut skills = Vec::new();
 
@@ -187,7 +115,7 @@ fn scan_skills_directory(
 
         // Un skill est un dossier contenant SKILL.md
         if path.is_dir() && path.join("SKILL.md").exists() {
-            match load_skill(path, source.clone(), project_name.clone()) {
+            match load_skill_with(path, source.clone(), project_name.clone(), provenance) {
                 Ok(skill) => skills.push(skill),
                 Err(e) => {
                     #[cfg(debug_assertions)]
@@ -201,10 +129,21 @@ fn scan_skills_directory(
 }
 
 /// Charge un skill depuis un dossier (lit SKILL.md à l'intérieur)
-fn load_skill(
+pub fn load_skill(
+    folder_path: &Path,
+    source: SkillSource,
+    project_name: Option<String>,
+) -> anyhow::Result<Skill> {
+    let provenance = Config::load().map(|config| load_provenance(&config)).unwrap_or_default();
+    load_skill_with(folder_path, source, project_name, &provenance)
+}
+
+/// `load_skill` avec un registre de provenance déjà chargé (listing de nombreux skills)
+fn load_skill_with(
     folder_path: &Path,
     source: SkillSource,
     project_name: Option<String>,
+    provenance: &SkillProvenance,
 ) -> anyhow::Result<Skill> {
     let skill_md = folder_path.join("SKILL.md");
     let content = fs::read_to_string(&skill_md)?;
@@ -229,6 +168,9 @@ fn load_skill(
         frontmatter.name
     };
 
+    let trusted = trusted_source(folder_path, &source, provenance);
+    let trust_level = trust_level(trusted, &scan_skill_cached(folder_path));
+
     Ok(Skill {
         id,
         name,
@@ -239,52 +181,70 @@ fn load_skill(
         content: markdown,
         last_modified,
         linked_projects: Vec::new(),
+        trust_level,
+        usage_count: 0,
+        last_used: None,
     })
 }
 
-/// Charge une commande depuis un fichier .md
-fn load_command(
-    file_path: &Path,
-    source: SkillSource,
-    project_name: Option<String>,
-) -> anyhow::Result<Skill> {
-    let content = fs::read_to_string(file_path)?;
-    let (frontmatter, markdown) = parse_frontmatter(&content)?;
+/// Source sûre : skill approuvé et inchangé depuis, ou skill global écrit sur place
+/// (un lien global vers un dépôt tiers doit être approuvé)
+fn trusted_source(folder_path: &Path, source: &SkillSource, provenance: &SkillProvenance) -> bool {
+    let is_symlink = fs::symlink_metadata(folder_path).map(|m| m.is_symlink()).unwrap_or(true);
+    provenance.is_vouched(folder_path) || (matches!(source, SkillSource::Global) && !is_symlink)
+}
 
-    let path_str = file_path.to_string_lossy().to_string();
-    let id = Skill::generate_id(&path_str);
+/// Registre de provenance ; s'il est illisible, aucun skill n'est considéré comme approuvé
+fn load_provenance(config: &Config) -> SkillProvenance {
+    SkillProvenance::load(Path::new(&config.expanded_skill_provenance_path())).unwrap_or_else(|_e| {
+        #[cfg(debug_assertions)]
+        eprintln!("⚠️ {} : registre ignoré", _e);
+        SkillProvenance::default()
+    })
+}
 
-    let metadata = fs::metadata(file_path)?;
-    let last_modified = metadata
-        .modified()?
-        .duration_since(std::time::UNIX_EPOCH)?
-        .as_secs();
+fn is_vouched(folder_path: &Path) -> bool {
+    Config::load().is_ok_and(|config| load_provenance(&config).is_vouched(folder_path))
+}
 
-    Ok(Skill {
-        id,
-        name: frontmatter.name,
-        description: frontmatter.description.unwrap_or_default(),
-        path: path_str,
-        source,
-        project_name,
-        content: markdown,
-        last_modified,
-        linked_projects: Vec::new(),
-    })
+/// Enregistre le contenu actuel d'un skill comme approuvé
+fn vouch_skill(folder_path: &Path) -> Result<(), String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    let path = PathBuf::from(config.expanded_skill_provenance_path());
+    let mut provenance =
+        SkillProvenance::load(&path).map_err(|e| format!("Erreur lecture provenance: {}", e))?;
+    provenance.vouch(folder_path).map_err(|e| format!("Erreur empreinte du skill: {}", e))?;
+    provenance.save(&path).map_err(|e| format!("Erreur écriture provenance: {}", e))
+}
+
+/// Marque un skill comme approuvé après relecture ; toute modification ultérieure hors de
+/// l'application retire cette approbation
+#[tauri::command]
+pub async fn trust_skill(path: String) -> Result<Skill, String> {
+    let folder_path = PathBuf::from(&path);
+    if !folder_path.join("SKILL.md").is_file() {
+        return Err("SKILL.md introuvable dans ce dossier".to_string());
+    }
+    vouch_skill(&folder_path)?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("🛡️ Skill approuvé: {}", folder_path.display());
+
+    get_skill(path).await
 }
 
-/// Crée un symlink d'un dossier skill vers un projet
+/// Crée un symlink d'un dossier skill vers un projet (cible absolue par défaut, ou relative)
 #[tauri::command]
 pub async fn create_skill_symlink(
     source_path: String,
     target_project_path: String,
+    link_style: Option<LinkStyle>,
 ) -> Result<String, String> {
-    use std::os::unix::fs::symlink;
-
     let source = PathBuf::from(&source_path);
     if !source.exists() {
         return Err("Le dossier source n'existe pas".to_string());
     }
+    ensure_no_secrets_for_repo(&source, Path::new(&target_project_path))?;
 
     let target_dir = PathBuf::from(&target_project_path)
         .join(CLAUDE_DIR)
@@ -298,14 +258,14 @@ pub async fn create_skill_symlink(
         .ok_or("Impossible d'extraire le nom du dossier")?;
     let target_path = target_dir.join(folder_name);
 
-    if target_path.exists() {
+    if fs::symlink_metadata(&target_path).is_ok() {
         return Err(format!(
             "Un skill avec ce nom existe déjà dans {}",
             target_project_path
         ));
     }
 
-    symlink(&source, &target_path)
+    create_symlink(&source, &target_path, link_style.unwrap_or_default())
         .map_err(|e| format!("Erreur création symlink: {}", e))?;
 
     #[cfg(debug_assertions)]
@@ -327,6 +287,8 @@ pub async fn duplicate_skill(
         return Err("Le dossier source n'existe pas".to_string());
     }
 
+    let source_vouched = is_vouched(&source);
+
     let source_skill_md = source.join("SKILL.md");
     let source_content = fs::read_to_string(&source_skill_md)
         .map_err(|e| format!("Erreur lecture SKILL.md: {}", e))?;
@@ -343,6 +305,7 @@ pub async fn duplicate_skill(
         let project_path = destination_project
             .clone()
             .ok_or("Chemin du projet requis pour destination project")?;
+        ensure_no_secrets_for_repo(&source, Path::new(&project_path))?;
         PathBuf::from(project_path)
             .join(CLAUDE_DIR)
             .join(SKILLS_DIR)
@@ -363,6 +326,11 @@ pub async fn duplicate_skill(
     fs::write(&skill_md_path, &new_content)
         .map_err(|e| format!("Erreur écriture SKILL.md: {}", e))?;
 
+    // La copie d'un skill approuvé reste approuvée
+    if source_vouched {
+        vouch_skill(&destination_dir)?;
+    }
+
     #[cfg(debug_assertions)]
     eprintln!("📋 Skill dupliqué: {}", destination_dir.display());
 
@@ -375,6 +343,100 @@ pub async fn duplicate_skill(
     load_skill(&destination_dir, source_type, destination_project).map_err(|e| e.to_string())
 }
 
+/// Liste les modèles de skills (intégrés + dossier de modèles utilisateur)
+#[tauri::command]
+pub async fn list_skill_templates() -> Result<Vec<SkillTemplate>, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+    let mut templates = builtin_templates();
+    templates.extend(load_user_templates(Path::new(&config.expanded_templates_path())));
+    Ok(templates)
+}
+
+/// Crée un nouveau skill à partir d'un modèle
+#[tauri::command]
+pub async fn create_skill(
+    name: String,
+    destination_type: String,
+    destination_project: Option<String>,
+    template: String,
+    description: Option<String>,
+    folders: Option<Vec<String>>,
+) -> Result<Skill, String> {
+    let config = Config::load().map_err(|e| format!("Erreur chargement config: {}", e))?;
+
+    let skill_name = base_name_to_kebab(&name);
+    if skill_name.is_empty() {
+        return Err("Le nom du skill est vide".to_string());
+    }
+    if skill_name.starts_with("claude-") || skill_name.starts_with("anthropic-") {
+        return Err("Les préfixes 'claude-' et 'anthropic-' sont réservés".to_string());
+    }
+
+    let mut templates = builtin_templates();
+    templates.extend(load_user_templates(Path::new(&config.expanded_templates_path())));
+    let template = templates
+        .into_iter()
+        .find(|t| t.id == template)
+        .ok_or_else(|| format!("Modèle inconnu: {}", template))?;
+
+    let folders = folders.unwrap_or_else(|| template.folders.clone());
+    if let Some(invalid) = folders.iter().find(|f| !STRUCTURE_FOLDERS.contains(&f.as_str())) {
+        return Err(format!("Dossier non autorisé: {} (scripts, references ou assets)", invalid));
+    }
+
+    let (destination_base, source_type) = if destination_type == "global" {
+        (PathBuf::from(config.expanded_global_skills_path()), SkillSource::Global)
+    } else {
+        let project_path = destination_project
+            .clone()
+            .ok_or("Chemin du projet requis pour destination project")?;
+        (
+            PathBuf::from(project_path).join(CLAUDE_DIR).join(SKILLS_DIR),
+            SkillSource::Project,
+        )
+    };
+
+    let skill_dir = destination_base.join(&skill_name);
+    if skill_dir.exists() {
+        return Err(format!("Un skill nommé '{}' existe déjà à cet emplacement", skill_name));
+    }
+
+    let category = if template.category.is_empty() || !template.builtin {
+        detect_category(&skill_name, description.as_deref().unwrap_or(""))
+    } else {
+        template.category.clone()
+    };
+    let vars = TemplateVars {
+        name: skill_name,
+        description,
+        category,
+        author: std::env::var("USER").unwrap_or_else(|_| "unknown".into()),
+    };
+
+    scaffold_skill(&skill_dir, &template, &vars, &folders)
+        .map_err(|e| format!("Erreur création skill: {}", e))?;
+
+    let project_name = destination_project
+        .as_deref()
+        .filter(|_| matches!(source_type, SkillSource::Project))
+        .and_then(|p| Path::new(p).file_name())
+        .and_then(|n| n.to_str())
+        .map(String::from);
+    let skill = vouch_skill(&skill_dir)
+        .and_then(|_| load_skill(&skill_dir, source_type, project_name).map_err(|e| e.to_string()));
+    // Pas de skill à moitié créé : le dossier est retiré si l'enregistrement échoue
+    if skill.is_err() {
+        let _ = fs::remove_dir_all(&skill_dir);
+    }
+
+    #[cfg(debug_assertions)]
+    if skill.is_ok() {
+        eprintln!("✨ Skill créé: {} (modèle {})", skill_dir.display(), template.id);
+    }
+
+    skill
+}
+
 /// Supprime un skill (dossier ou symlink)
 #[tauri::command]
 pub async fn delete_skill(path: String) -> Result<(), String> {
@@ -421,9 +483,14 @@ pub async fn update_skill(
         return Err("SKILL.md introuvable dans ce dossier".to_string());
     }
 
+    let was_vouched = is_vouched(&folder_path);
+
     let new_content = build_skill_content(&name, description.as_deref(), &content);
     fs::write(&skill_md, &new_content)
         .map_err(|e| format!("Erreur écriture: {}", e))?;
+    if was_vouched {
+        vouch_skill(&folder_path)?;
+    }
 
     #[cfg(debug_assertions)]
     eprintln!("✏️ Skill mis à jour: {}", skill_md.display());
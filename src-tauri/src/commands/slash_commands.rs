@@ -0,0 +1,806 @@
+use crate::models::command::{Command, CommandFrontmatter, CommandScope};
+use crate::models::skill::{Skill, SkillSource};
+use crate::utils::command_template::{
+    apply_bash_outputs, extract_bash_lines, inline_file_references, substitute_arguments, BashLine,
+    FileReference,
+};
+use crate::utils::symlinks::{create_symlink, intended_target, relative_target, LinkStyle};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use walkdir::WalkDir;
+
+use super::links::{link_map, links_to};
+use super::security::ensure_no_secrets_for_repo;
+use super::skills::resolve_source;
+use super::{CLAUDE_DIR, COMMANDS_DIR};
+
+/// Durée maximale d'exécution d'une ligne bash dans l'aperçu
+const PREVIEW_BASH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Taille maximale de la sortie conservée par ligne bash
+const PREVIEW_OUTPUT_BYTES: usize = 16 * 1024;
+/// Outil d'isolation (Linux) utilisé pour exécuter les lignes bash de l'aperçu
+const SANDBOX_PROGRAM: &str = "bwrap";
+
+/// Champs éditables d'une commande (frontmatter + contenu Markdown)
+#[derive(Debug, Deserialize)]
+pub struct CommandFields {
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    pub model: Option<String>,
+    #[serde(default)]
+    pub disable_model_invocation: bool,
+    #[serde(default)]
+    pub content: String,
+}
+
+/// Commande de projet dont l'invocation est déjà utilisée par une commande utilisateur
+#[derive(Debug, Serialize)]
+pub struct CommandCollision {
+    pub invocation: String,
+    pub project_name: String,
+    pub project_path: String,
+    pub user_command_path: String,
+    pub project_command_path: String,
+}
+
+/// Prompt final d'une commande après expansion
+#[derive(Debug, Serialize)]
+pub struct CommandPreview {
+    pub invocation: String,
+    pub arguments: Vec<String>,
+    pub prompt: String,
+    pub bash: Vec<BashLine>,
+    pub files: Vec<FileReference>,
+    pub executed: bool,
+}
+
+/// Liste toutes les commandes (globales + projets)
+#[tauri::command]
+pub async fn list_commands(project_paths: Vec<String>) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::new();
+    let symlink_map = link_map(&project_paths, COMMANDS_DIR);
+
+    let global_commands_path = global_commands_dir()?;
+    if let Ok(global_cmds) = scan_commands_directory(&global_commands_path, SkillSource::Global, None) {
+        commands.extend(global_cmds);
+    }
+
+    for project_path in &project_paths {
+        let project_cmds_path = PathBuf::from(project_path)
+            .join(CLAUDE_DIR)
+            .join(COMMANDS_DIR);
+        if project_cmds_path.exists() {
+            let project_name = PathBuf::from(project_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(String::from);
+            if let Ok(cmds) = scan_commands_directory(&project_cmds_path, SkillSource::Project, project_name) {
+                commands.extend(cmds);
+            }
+        }
+    }
+
+    for command in &mut commands {
+        let is_symlink = fs::symlink_metadata(&command.path).map(|m| m.is_symlink()).unwrap_or(false);
+        if is_symlink {
+            continue;
+        }
+        if let Some(projects) = fs::canonicalize(&command.path).ok().and_then(|p| symlink_map.get(&p)) {
+            command.linked_projects = projects.clone();
+        }
+    }
+
+    Ok(commands)
+}
+
+fn scan_commands_directory(
+    base_path: &Path,
+    source: SkillSource,
+    project_name: Option<String>,
+) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::new();
+
+    if !base_path.exists() {
+        return Ok(commands);
+    }
+
+    // Pas de limite de profondeur : chaque sous-dossier ajoute un niveau de namespace
+    for entry in WalkDir::new(base_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
+            match load_command(path, source.clone(), project_name.clone()) {
+                Ok(command) => commands.push(command),
+                Err(e) => {
+                    #[cfg(debug_assertions)]
+                    eprintln!("Error loading command {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+/// Charge une commande depuis un fichier .md (le nom est celui du fichier, le namespace
+/// celui de ses sous-dossiers sous `.claude/commands`)
+pub fn load_command(
+    file_path: &Path,
+    source: SkillSource,
+    project_name: Option<String>,
+) -> anyhow::Result<Command> {
+    let content = fs::read_to_string(file_path)?;
+    let (frontmatter, markdown) = CommandFrontmatter::parse(&content);
+
+    let name = file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Untitled")
+        .to_string();
+    let namespace = command_namespace(file_path);
+    let invocation = Command::invocation_for(namespace.as_deref(), &name);
+
+    let path_str = file_path.to_string_lossy().to_string();
+    let id = Skill::generate_id(&path_str);
+
+    let metadata = fs::metadata(file_path)?;
+    let last_modified = metadata
+        .modified()?
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+
+    Ok(Command {
+        id,
+        name,
+        namespace,
+        invocation,
+        scope: CommandScope::from(&source),
+        description: frontmatter.description.unwrap_or_default(),
+        path: path_str,
+        source,
+        project_name,
+        content: markdown,
+        last_modified,
+        linked_projects: Vec::new(),
+        argument_hint: frontmatter.argument_hint,
+        allowed_tools: frontmatter.allowed_tools,
+        model: frontmatter.model,
+        disable_model_invocation: frontmatter.disable_model_invocation.unwrap_or(false),
+        extra_frontmatter: frontmatter.extra,
+        usage_count: 0,
+        last_used: None,
+    })
+}
+
+/// Détecte les commandes de projet qui masquent une commande utilisateur de même invocation
+///
+/// Les symlinks vers la commande utilisateur elle-même ne sont pas des conflits.
+#[tauri::command]
+pub async fn analyze_command_collisions(project_paths: Vec<String>) -> Result<Vec<CommandCollision>, String> {
+    command_collisions(&global_commands_dir()?, project_paths)
+}
+
+/// Collisions entre les commandes du dossier utilisateur `user_dir` et celles des projets
+pub(crate) fn command_collisions(
+    user_dir: &Path,
+    project_paths: Vec<String>,
+) -> Result<Vec<CommandCollision>, String> {
+    let user_commands = scan_commands_directory(user_dir, SkillSource::Global, None)?;
+    let mut collisions = Vec::new();
+
+    for project_path in project_paths {
+        let project_dir = PathBuf::from(&project_path);
+        let project_name = project_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Projet")
+            .to_string();
+        let project_commands = scan_commands_directory(
+            &project_dir.join(CLAUDE_DIR).join(COMMANDS_DIR),
+            SkillSource::Project,
+            Some(project_name.clone()),
+        )?;
+
+        for project_command in &project_commands {
+            for user_command in user_commands.iter().filter(|c| c.invocation == project_command.invocation) {
+                if same_file(&user_command.path, &project_command.path) {
+                    continue;
+                }
+                collisions.push(CommandCollision {
+                    invocation: project_command.invocation.clone(),
+                    project_name: project_name.clone(),
+                    project_path: project_path.clone(),
+                    user_command_path: user_command.path.clone(),
+                    project_command_path: project_command.path.clone(),
+                });
+            }
+        }
+    }
+
+    collisions.sort_by(|a, b| a.invocation.cmp(&b.invocation));
+    Ok(collisions)
+}
+
+/// Aperçu du prompt envoyé par `/commande <arguments>` dans un projet
+///
+/// Les arguments sont substitués, les fichiers `@` inlinés (taille limitée) et les lignes bash
+/// listées avec leur statut vis-à-vis d'`allowed-tools`, sans être lancées. Seul `execute: true`
+/// les exécute (lignes autorisées uniquement), isolées par bubblewrap : système et projet en
+/// lecture seule, dossier personnel masqué, sans réseau, sans stdin et avec un timeout.
+/// Sans bubblewrap, l'exécution est refusée.
+#[tauri::command]
+pub async fn preview_command(
+    path: String,
+    arguments: String,
+    project_path: String,
+    execute: Option<bool>,
+) -> Result<CommandPreview, String> {
+    let command = get_command(path).await?;
+    let project_dir = PathBuf::from(&project_path);
+    if !project_dir.is_dir() {
+        return Err("Le dossier du projet n'existe pas".to_string());
+    }
+
+    let positional = shell_words::split(&arguments)
+        .unwrap_or_else(|_| arguments.split_whitespace().map(String::from).collect());
+    let substituted = substitute_arguments(&command.content, arguments.trim(), &positional);
+
+    let mut bash = extract_bash_lines(&substituted, &command.allowed_tools);
+    let execute = execute.unwrap_or(false);
+    if execute && bash.iter().any(|l| l.allowed) {
+        let project = project_dir
+            .canonicalize()
+            .map_err(|e| format!("Erreur lecture du dossier projet: {}", e))?;
+        if !sandbox_available().await {
+            return Err(format!(
+                "Exécution isolée indisponible : {} (bubblewrap) introuvable",
+                SANDBOX_PROGRAM
+            ));
+        }
+        for line in bash.iter_mut().filter(|l| l.allowed) {
+            run_preview_bash(line, &project).await;
+        }
+    }
+
+    let with_outputs = apply_bash_outputs(&substituted, &bash);
+    let (prompt, files) = inline_file_references(&with_outputs, &project_dir);
+
+    Ok(CommandPreview {
+        invocation: command.invocation,
+        arguments: positional,
+        prompt,
+        bash,
+        files,
+        executed: execute,
+    })
+}
+
+async fn sandbox_available() -> bool {
+    tokio::process::Command::new(SANDBOX_PROGRAM)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|s| s.success())
+}
+
+/// Arguments de bubblewrap : racine en lecture seule, HOME et /tmp vides et jetables, projet
+/// remonté en lecture seule, tous les espaces de noms (réseau compris) isolés
+fn sandbox_args(project: &Path, home: Option<&Path>) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> =
+        ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc", "--tmpfs", "/tmp"]
+            .iter()
+            .map(Into::into)
+            .collect();
+    if let Some(home) = home {
+        args.extend(["--tmpfs".into(), home.as_os_str().to_owned()]);
+    }
+    args.extend(["--ro-bind".into(), project.as_os_str().to_owned(), project.as_os_str().to_owned()]);
+    args.extend(["--chdir".into(), project.as_os_str().to_owned()]);
+    args.extend(["--unshare-all", "--die-with-parent", "--new-session"].iter().map(Into::into));
+    args
+}
+
+/// Lance une ligne dans bubblewrap, avec un environnement minimal
+async fn run_preview_bash(line: &mut BashLine, project: &Path) {
+    let home = std::env::var("HOME").ok().map(PathBuf::from);
+    let mut process = tokio::process::Command::new(SANDBOX_PROGRAM);
+    process
+        .args(sandbox_args(project, home.as_deref()))
+        .args(["sh", "-c", &line.command])
+        .env_clear()
+        .env("PATH", std::env::var("PATH").unwrap_or_default())
+        .env("HOME", home.as_deref().unwrap_or(Path::new("/tmp")))
+        .env("LANG", "C.UTF-8")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    match tokio::time::timeout(PREVIEW_BASH_TIMEOUT, process.output()).await {
+        Ok(Ok(output)) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            if text.len() > PREVIEW_OUTPUT_BYTES {
+                let cut = (0..=PREVIEW_OUTPUT_BYTES).rev().find(|i| text.is_char_boundary(*i)).unwrap_or(0);
+                text.truncate(cut);
+                text.push_str("\n[… sortie tronquée]");
+            }
+            line.output = Some(text);
+            line.exit_code = output.status.code();
+        }
+        Ok(Err(e)) => line.error = Some(format!("Erreur exécution: {}", e)),
+        Err(_) => line.error = Some(format!("Timeout après {} s", PREVIEW_BASH_TIMEOUT.as_secs())),
+    }
+}
+
+/// Récupère une commande par le chemin de son fichier .md
+#[tauri::command]
+pub async fn get_command(path: String) -> Result<Command, String> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.is_file() {
+        return Err("Le fichier de commande n'existe pas".to_string());
+    }
+    let (source, project_name) = resolve_source(&file_path, COMMANDS_DIR)?;
+
+    load_command(&file_path, source, project_name).map_err(|e| e.to_string())
+}
+
+/// Crée une nouvelle commande (~/.claude/commands ou <projet>/.claude/commands)
+#[tauri::command]
+pub async fn create_command(
+    name: String,
+    destination_type: String,
+    destination_project: Option<String>,
+    fields: CommandFields,
+) -> Result<Command, String> {
+    let relative = command_file(&name)?;
+
+    let (destination_dir, source, project_name) =
+        destination_commands_dir(&destination_type, destination_project.as_deref())?;
+    let file_path = destination_dir.join(relative);
+    if fs::symlink_metadata(&file_path).is_ok() {
+        return Err(format!("Une commande nommée '{}' existe déjà à cet emplacement", name));
+    }
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création répertoire: {}", e))?;
+    }
+
+    let mut frontmatter = CommandFrontmatter::default();
+    apply_fields(&mut frontmatter, &fields);
+    let new_content = build_command_content(&frontmatter, &fields.content)?;
+    fs::write(&file_path, &new_content)
+        .map_err(|e| format!("Erreur écriture commande: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("✨ Commande créée: {}", file_path.display());
+
+    load_command(&file_path, source, project_name).map_err(|e| e.to_string())
+}
+
+/// Met à jour une commande : les clés de frontmatter non gérées sont conservées
+#[tauri::command]
+pub async fn update_command(path: String, fields: CommandFields) -> Result<Command, String> {
+    let file_path = PathBuf::from(&path);
+    let existing = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Erreur lecture commande: {}", e))?;
+
+    let (mut frontmatter, _) = CommandFrontmatter::parse(&existing);
+    apply_fields(&mut frontmatter, &fields);
+
+    let new_content = build_command_content(&frontmatter, &fields.content)?;
+    fs::write(&file_path, &new_content)
+        .map_err(|e| format!("Erreur écriture: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("✏️ Commande mise à jour: {}", file_path.display());
+
+    get_command(path).await
+}
+
+/// Supprime une commande (fichier ou symlink)
+///
+/// Les liens des projets fournis vers la commande supprimée sont retirés avec elle plutôt que
+/// laissés pendants ; leurs chemins sont renvoyés.
+#[tauri::command]
+pub async fn delete_command(path: String, project_paths: Option<Vec<String>>) -> Result<Vec<String>, String> {
+    let file_path = PathBuf::from(&path);
+
+    let metadata = fs::symlink_metadata(&file_path)
+        .map_err(|_| "Le fichier de commande n'existe pas".to_string())?;
+    if metadata.is_dir() {
+        return Err("Le chemin désigne un dossier, pas une commande".to_string());
+    }
+    // Cibles relevées avant la suppression : un lien pendant n'a plus de chemin canonique
+    let links = match (metadata.is_symlink(), file_path.canonicalize()) {
+        (false, Ok(target)) => links_to(&target, &project_paths.unwrap_or_default(), COMMANDS_DIR),
+        _ => Vec::new(),
+    };
+
+    #[cfg(debug_assertions)]
+    eprintln!("🗑️ Suppression de la commande: {}", file_path.display());
+
+    fs::remove_file(&file_path).map_err(|e| format!("Erreur suppression commande: {}", e))?;
+
+    let mut removed = Vec::new();
+    for link in links {
+        match fs::remove_file(&link) {
+            Ok(()) => removed.push(link.to_string_lossy().to_string()),
+            Err(_e) => {
+                #[cfg(debug_assertions)]
+                eprintln!("⚠️ Lien non supprimé {}: {}", link.display(), _e);
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Duplique une commande (frontmatter complet conservé) vers une nouvelle destination
+#[tauri::command]
+pub async fn duplicate_command(
+    source_path: String,
+    destination_type: String,
+    destination_project: Option<String>,
+    new_name: String,
+) -> Result<Command, String> {
+    let relative = command_file(&new_name)?;
+
+    let source = PathBuf::from(&source_path);
+    let source_content = fs::read_to_string(&source)
+        .map_err(|e| format!("Erreur lecture commande: {}", e))?;
+    if let Some(project) = destination_project.as_deref().filter(|_| destination_type != "global") {
+        ensure_no_secrets_for_repo(&source, Path::new(project))?;
+    }
+
+    let (destination_dir, source_type, project_name) =
+        destination_commands_dir(&destination_type, destination_project.as_deref())?;
+    let destination = destination_dir.join(relative);
+    if fs::symlink_metadata(&destination).is_ok() {
+        return Err(format!("Une commande nommée '{}' existe déjà à cet emplacement", new_name));
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création répertoire: {}", e))?;
+    }
+    fs::write(&destination, &source_content)
+        .map_err(|e| format!("Erreur écriture commande: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("📋 Commande dupliquée: {}", destination.display());
+
+    load_command(&destination, source_type, project_name).map_err(|e| e.to_string())
+}
+
+/// Crée un symlink d'une commande vers un projet (sous-dossier de namespace conservé)
+#[tauri::command]
+pub async fn create_command_symlink(
+    source_path: String,
+    target_project_path: String,
+    link_style: Option<LinkStyle>,
+) -> Result<String, String> {
+    let source = PathBuf::from(&source_path);
+    if !source.is_file() {
+        return Err("Le fichier source n'existe pas".to_string());
+    }
+    ensure_no_secrets_for_repo(&source, Path::new(&target_project_path))?;
+
+    let target_path = PathBuf::from(&target_project_path)
+        .join(CLAUDE_DIR)
+        .join(COMMANDS_DIR)
+        .join(relative_command_path(&source)?);
+
+    if fs::symlink_metadata(&target_path).is_ok() {
+        return Err(format!(
+            "Une commande avec ce nom existe déjà dans {}",
+            target_project_path
+        ));
+    }
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création répertoire: {}", e))?;
+    }
+
+    create_symlink(&source, &target_path, link_style.unwrap_or_default())
+        .map_err(|e| format!("Erreur création symlink: {}", e))?;
+
+    #[cfg(debug_assertions)]
+    eprintln!("🔗 Symlink créé: {} -> {}", target_path.display(), source.display());
+
+    Ok(target_path.to_string_lossy().to_string())
+}
+
+/// Déplace (et renomme éventuellement) une commande vers une autre destination
+#[tauri::command]
+pub async fn move_command(
+    source_path: String,
+    destination_type: String,
+    destination_project: Option<String>,
+    new_name: Option<String>,
+) -> Result<Command, String> {
+    let source = PathBuf::from(&source_path);
+    if fs::symlink_metadata(&source).is_err() {
+        return Err("Le fichier de commande n'existe pas".to_string());
+    }
+    if let Some(project) = destination_project.as_deref().filter(|_| destination_type != "global") {
+        ensure_no_secrets_for_repo(&source, Path::new(project))?;
+    }
+
+    let (destination_dir, source_type, project_name) =
+        destination_commands_dir(&destination_type, destination_project.as_deref())?;
+    let relative = relative_command_path(&source)?;
+    // Un nouveau nom sans namespace garde celui de la commande ; `ns:nom` le remplace
+    let destination = match &new_name {
+        Some(name) if name.contains(':') => destination_dir.join(command_file(name)?),
+        Some(name) => destination_dir
+            .join(relative.parent().unwrap_or(Path::new("")))
+            .join(command_file(name)?),
+        None => destination_dir.join(relative),
+    };
+
+    if destination == source {
+        return load_command(&source, source_type, project_name).map_err(|e| e.to_string());
+    }
+    if fs::symlink_metadata(&destination).is_ok() {
+        return Err("Une commande avec ce nom existe déjà à cet emplacement".to_string());
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création répertoire: {}", e))?;
+    }
+
+    let is_symlink = fs::symlink_metadata(&source).map(|m| m.is_symlink()).unwrap_or(false);
+    if is_symlink {
+        move_link(&source, &destination)?;
+    } else if fs::rename(&source, &destination).is_err() {
+        // rename échoue entre deux systèmes de fichiers : repli sur copie + suppression
+        fs::copy(&source, &destination).map_err(|e| format!("Erreur copie commande: {}", e))?;
+        fs::remove_file(&source).map_err(|e| format!("Erreur suppression source: {}", e))?;
+    }
+
+    #[cfg(debug_assertions)]
+    eprintln!("📦 Commande déplacée: {} -> {}", source.display(), destination.display());
+
+    load_command(&destination, source_type, project_name).map_err(|e| e.to_string())
+}
+
+/// Déplace un symlink : une cible relative est recalculée depuis le nouveau dossier du lien
+fn move_link(source: &Path, destination: &Path) -> Result<(), String> {
+    let raw = fs::read_link(source).map_err(|e| format!("Erreur lecture lien: {}", e))?;
+    let written = if raw.is_absolute() || raw.starts_with("~") {
+        raw
+    } else {
+        let target = intended_target(source).map_err(|e| format!("Erreur lecture lien: {}", e))?;
+        relative_target(destination, &target)
+    };
+    std::os::unix::fs::symlink(written, destination).map_err(|e| format!("Erreur création symlink: {}", e))?;
+    fs::remove_file(source).map_err(|e| format!("Erreur suppression source: {}", e))
+}
+
+fn global_commands_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME").map_err(|_| "Variable HOME non définie".to_string())?;
+    Ok(PathBuf::from(home).join(CLAUDE_DIR).join(COMMANDS_DIR))
+}
+
+/// Dossier de commandes de la destination, avec la source et le nom du projet associés
+fn destination_commands_dir(
+    destination_type: &str,
+    destination_project: Option<&str>,
+) -> Result<(PathBuf, SkillSource, Option<String>), String> {
+    if destination_type == "global" {
+        return Ok((global_commands_dir()?, SkillSource::Global, None));
+    }
+
+    let project_path = destination_project.ok_or("Chemin du projet requis pour destination project")?;
+    let project_name = Path::new(project_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from);
+    Ok((
+        PathBuf::from(project_path).join(CLAUDE_DIR).join(COMMANDS_DIR),
+        SkillSource::Project,
+        project_name,
+    ))
+}
+
+/// Chemin de la commande relatif à son dossier `.claude/commands` (namespace compris)
+pub fn relative_command_path(path: &Path) -> Result<PathBuf, String> {
+    let root = path
+        .ancestors()
+        .find(|p| {
+            p.file_name().and_then(|n| n.to_str()) == Some(COMMANDS_DIR)
+                && p.parent().and_then(|pp| pp.file_name()).and_then(|n| n.to_str()) == Some(CLAUDE_DIR)
+        })
+        .ok_or("La commande n'est pas dans un dossier .claude/commands")?;
+
+    path.strip_prefix(root)
+        .map(Path::to_path_buf)
+        .map_err(|e| e.to_string())
+}
+
+/// Namespace d'une commande : ses sous-dossiers relatifs à `.claude/commands`, joints par ':'
+fn command_namespace(file_path: &Path) -> Option<String> {
+    let relative = relative_command_path(file_path).ok()?;
+    let parts: Vec<&str> = relative
+        .parent()?
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(":"))
+    }
+}
+
+fn same_file(a: &str, b: &str) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Fichier d'une commande relatif à `.claude/commands` : `git:commit` → `git/commit.md`
+///
+/// Chaque segment devient un nom de fichier ou de dossier : `/git:commit` doit rester invocable.
+fn command_file(name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() {
+        return Err("Le nom de la commande est vide".to_string());
+    }
+    let valid_segment = |segment: &str| {
+        !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    if !name.split(':').all(valid_segment) {
+        return Err(format!(
+            "Nom de commande invalide: {} (lettres, chiffres, - et _ uniquement, ':' pour un namespace)",
+            name
+        ));
+    }
+    let mut file: PathBuf = name.split(':').collect();
+    file.set_extension("md");
+    Ok(file)
+}
+
+fn apply_fields(frontmatter: &mut CommandFrontmatter, fields: &CommandFields) {
+    let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+
+    frontmatter.description = non_empty(&fields.description);
+    frontmatter.argument_hint = non_empty(&fields.argument_hint);
+    frontmatter.allowed_tools = fields.allowed_tools.clone();
+    frontmatter.model = non_empty(&fields.model);
+    frontmatter.disable_model_invocation = fields.disable_model_invocation.then_some(true);
+}
+
+fn build_command_content(frontmatter: &CommandFrontmatter, content: &str) -> Result<String, String> {
+    let yaml = frontmatter
+        .to_yaml()
+        .map_err(|e| format!("Erreur sérialisation frontmatter: {}", e))?;
+    if yaml.is_empty() {
+        return Ok(content.to_string());
+    }
+    Ok(format!("---\n{}---\n\n{}", yaml, content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_args_hide_home_and_network() {
+        let args = sandbox_args(Path::new("/home/me/app"), Some(Path::new("/home/me")));
+        let args: Vec<&str> = args.iter().map(|a| a.to_str().unwrap()).collect();
+        let joined = args.join(" ");
+        assert!(joined.starts_with("--ro-bind / / "));
+        // HOME est masqué avant que le projet, qui peut s'y trouver, soit remonté en lecture seule
+        let home = joined.find("--tmpfs /home/me").unwrap();
+        let project = joined.find("--ro-bind /home/me/app /home/me/app").unwrap();
+        assert!(home < project);
+        assert!(args.contains(&"--unshare-all"));
+        assert!(!args.contains(&"--share-net") && !args.contains(&"--bind"));
+    }
+
+    #[test]
+    fn test_move_link_keeps_relative_target_valid() {
+        let root = std::env::temp_dir().join(format!("move-link-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let commands = root.join("app/.claude/commands");
+        fs::create_dir_all(commands.join("git")).unwrap();
+        fs::create_dir_all(root.join("shared")).unwrap();
+        fs::write(root.join("shared/deploy.md"), "Deploy").unwrap();
+        std::os::unix::fs::symlink("../../../shared/deploy.md", commands.join("deploy.md")).unwrap();
+
+        move_link(&commands.join("deploy.md"), &commands.join("git/ship.md")).unwrap();
+        assert!(fs::symlink_metadata(commands.join("deploy.md")).is_err());
+        let raw = fs::read_link(commands.join("git/ship.md")).unwrap();
+        assert_eq!(raw, PathBuf::from("../../../../shared/deploy.md"));
+        assert_eq!(fs::read_to_string(commands.join("git/ship.md")).unwrap(), "Deploy");
+
+        let target = root.join("shared/deploy.md").canonicalize().unwrap();
+        let links = links_to(&target, &[root.join("app").to_string_lossy().to_string()], COMMANDS_DIR);
+        assert_eq!(links, vec![commands.join("git/ship.md")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn fields(description: &str, content: &str) -> CommandFields {
+        CommandFields {
+            description: Some(description.into()),
+            argument_hint: None,
+            allowed_tools: Vec::new(),
+            model: None,
+            disable_model_invocation: false,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn test_command_file_maps_namespaces() {
+        assert_eq!(command_file("deploy").unwrap(), PathBuf::from("deploy.md"));
+        assert_eq!(command_file("git:commit").unwrap(), PathBuf::from("git/commit.md"));
+        assert_eq!(command_file("a:b:c").unwrap(), PathBuf::from("a/b/c.md"));
+        for invalid in ["", "git:", ":commit", "git::commit", "../x", "a/b", "a b"] {
+            assert!(command_file(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_command_crud_in_project() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let root = std::env::temp_dir().join(format!("command-crud-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (app, web) = (root.join("app"), root.join("web"));
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(&web).unwrap();
+        let project = |p: &Path| Some(p.to_string_lossy().to_string());
+
+        let create = create_command("git:commit".into(), "project".into(), project(&app), fields("Commit", "Run git"));
+        let created = runtime.block_on(create).unwrap();
+        assert_eq!(created.invocation, "/git:commit");
+        assert_eq!(PathBuf::from(&created.path), app.join(".claude/commands/git/commit.md"));
+        let duplicate = create_command("git:commit".into(), "project".into(), project(&app), fields("x", "y"));
+        assert!(runtime.block_on(duplicate).is_err());
+
+        let update = update_command(created.path.clone(), fields("Commit all", "git commit -a"));
+        let updated = runtime.block_on(update).unwrap();
+        assert_eq!(updated.description, "Commit all");
+        assert_eq!(updated.content.trim(), "git commit -a");
+
+        let copy = runtime
+            .block_on(duplicate_command(created.path.clone(), "project".into(), project(&web), "ship".into()))
+            .unwrap();
+        assert_eq!(copy.invocation, "/ship");
+        assert_eq!(copy.description, "Commit all");
+
+        // Lien relatif de web vers la commande de app, déplacé avec une cible toujours valide
+        let link = web.join(".claude/commands/commit.md");
+        std::os::unix::fs::symlink("../../../app/.claude/commands/git/commit.md", &link).unwrap();
+        let link_path = link.to_string_lossy().to_string();
+        let moved = runtime
+            .block_on(move_command(link_path, "project".into(), project(&web), Some("tools:commit".into())))
+            .unwrap();
+        let moved_path = web.join(".claude/commands/tools/commit.md");
+        assert_eq!(PathBuf::from(&moved.path), moved_path);
+        let raw = fs::read_link(&moved_path).unwrap();
+        assert_eq!(raw, PathBuf::from("../../../../app/.claude/commands/git/commit.md"));
+        assert_eq!(moved.description, "Commit all");
+
+        // Suppression de la commande : le lien du projet web est retiré avec elle
+        let removed = runtime
+            .block_on(delete_command(created.path.clone(), Some(vec![web.to_string_lossy().to_string()])))
+            .unwrap();
+        assert_eq!(removed, vec![moved_path.to_string_lossy().to_string()]);
+        assert!(fs::symlink_metadata(&moved_path).is_err());
+        assert!(!app.join(".claude/commands/git/commit.md").exists());
+        assert!(web.join(".claude/commands/ship.md").is_file());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,236 @@
+use crate::utils::claude_state::{encode_project_dir, session_cwd, sessions_root, transcripts};
+use crate::utils::transcripts::{read_messages, snippet, summarize_session, SessionSummary, TranscriptMessage};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// Nombre de messages par événement "session-messages"
+const STREAM_CHUNK_SIZE: usize = 50;
+
+/// Lot de messages d'une session, émis dans l'événement "session-messages"
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionChunk {
+    pub session_id: String,
+    pub messages: Vec<TranscriptMessage>,
+    pub done: bool,
+}
+
+/// Occurrence trouvée dans une session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionHit {
+    pub session_id: String,
+    pub project_path: Option<String>,
+    pub message_index: usize,
+    pub role: String,
+    pub timestamp: Option<String>,
+    pub snippet: String,
+}
+
+/// Sessions Claude Code d'un projet, les plus récentes d'abord
+#[tauri::command]
+pub async fn list_sessions(project_path: String) -> Result<Vec<SessionSummary>, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut sessions: Vec<SessionSummary> = transcripts(&sessions_dir(Path::new(&project_path))?)
+            .iter()
+            .filter_map(|t| summarize_session(t).ok())
+            .collect();
+        sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(sessions)
+    })
+    .await
+    .map_err(|e| format!("Erreur lecture des sessions: {}", e))?
+}
+
+/// Envoie les messages d'une session par lots (événement "session-messages") et renvoie leur nombre
+#[tauri::command]
+pub async fn stream_session(app: AppHandle, project_path: String, session_id: String) -> Result<usize, String> {
+    let path = session_path(&sessions_dir(Path::new(&project_path))?, &session_id)?;
+    tokio::task::spawn_blocking(move || {
+        let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE);
+        let mut count = 0;
+        let emit = |messages, done| {
+            let _ = app.emit("session-messages", SessionChunk { session_id: session_id.clone(), messages, done });
+        };
+
+        read_messages(&path, |message| {
+            count += 1;
+            chunk.push(message);
+            if chunk.len() == STREAM_CHUNK_SIZE {
+                emit(std::mem::take(&mut chunk), false);
+            }
+            true
+        })
+        .map_err(|e| format!("Erreur lecture de la session: {}", e))?;
+        emit(chunk, true);
+
+        Ok(count)
+    })
+    .await
+    .map_err(|e| format!("Erreur lecture de la session: {}", e))?
+}
+
+/// Recherche plein texte (insensible à la casse) dans les sessions des projets donnés,
+/// ou de tous les projets connus de Claude Code si la liste est vide
+#[tauri::command]
+pub async fn search_sessions(
+    query: String,
+    project_paths: Vec<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SessionHit>, String> {
+    let needle = query.trim().to_lowercase();
+    let limit = limit.unwrap_or(50);
+    if needle.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let dirs: Vec<PathBuf> = if project_paths.is_empty() {
+            fs::read_dir(user_sessions_root()?)
+                .map(|rd| rd.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect())
+                .unwrap_or_default()
+        } else {
+            project_paths.iter().map(|p| sessions_dir(Path::new(p))).collect::<Result<_, _>>()?
+        };
+        let hits = search_dirs(&dirs, &needle, limit);
+
+        #[cfg(debug_assertions)]
+        eprintln!("🔎 Recherche dans les sessions \"{}\": {} résultats", query, hits.len());
+
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| format!("Erreur recherche dans les sessions: {}", e))?
+}
+
+/// Au plus `limit` occurrences de `needle` (en minuscules) dans les transcriptions des dossiers
+fn search_dirs(dirs: &[PathBuf], needle: &str, limit: usize) -> Vec<SessionHit> {
+    let mut hits = Vec::new();
+    if limit == 0 {
+        return hits;
+    }
+    for transcript in dirs.iter().flat_map(|d| transcripts(d)) {
+        let session_id = transcript.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+        let first_hit = hits.len();
+        let result = read_messages(&transcript, |message| {
+            if let Some(snippet) = snippet(&message.searchable_text(), needle) {
+                hits.push(SessionHit {
+                    session_id: session_id.clone(),
+                    project_path: None,
+                    message_index: message.index,
+                    role: message.role,
+                    timestamp: message.timestamp,
+                    snippet,
+                });
+            }
+            hits.len() < limit
+        });
+        if result.is_err() {
+            continue;
+        }
+        // Dossier de travail lu uniquement pour les sessions ayant des résultats
+        if hits.len() > first_hit {
+            let project_path = session_cwd(&transcript).map(|p| p.to_string_lossy().to_string());
+            for hit in &mut hits[first_hit..] {
+                hit.project_path = project_path.clone();
+            }
+        }
+        if hits.len() >= limit {
+            break;
+        }
+    }
+    hits
+}
+
+/// Dossier des transcriptions de l'utilisateur courant
+pub fn user_sessions_root() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME").map_err(|_| "Dossier personnel introuvable".to_string())?;
+    Ok(sessions_root(Path::new(&home)))
+}
+
+/// Dossier des transcriptions d'un projet (chemin tel quel, sinon chemin canonique)
+pub fn sessions_dir(project: &Path) -> Result<PathBuf, String> {
+    let root = user_sessions_root()?;
+    let direct = root.join(encode_project_dir(project));
+    if direct.is_dir() {
+        return Ok(direct);
+    }
+    Ok(project
+        .canonicalize()
+        .map(|canonical| root.join(encode_project_dir(&canonical)))
+        .ok()
+        .filter(|dir| dir.is_dir())
+        .unwrap_or(direct))
+}
+
+/// Fichier d'une session ; l'identifiant ne peut pas sortir du dossier des transcriptions
+fn session_path(sessions_dir: &Path, session_id: &str) -> Result<PathBuf, String> {
+    if session_id.is_empty() || Path::new(session_id).file_name().and_then(|n| n.to_str()) != Some(session_id) {
+        return Err(format!("Identifiant de session invalide: {}", session_id));
+    }
+    let path = sessions_dir.join(format!("{}.jsonl", session_id));
+    if !path.is_file() {
+        return Err(format!("Session introuvable: {}", session_id));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sessions(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sessions-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn message(role: &str, text: &str) -> String {
+        format!(
+            r#"{{"type":"{}","cwd":"/work/api","timestamp":"2025-06-01T10:00:00.000Z","message":{{"role":"{}","content":"{}"}}}}"#,
+            role, role, text
+        )
+    }
+
+    #[test]
+    fn test_session_path_stays_in_directory() {
+        let dir = sessions("path");
+        fs::write(dir.join("abc-123.jsonl"), "").unwrap();
+
+        assert_eq!(session_path(&dir, "abc-123").unwrap(), dir.join("abc-123.jsonl"));
+        for invalid in ["", "../x", "a/b", "/etc/passwd", ".."] {
+            let error = session_path(&dir, invalid).unwrap_err();
+            assert!(error.starts_with("Identifiant de session invalide"), "{}", invalid);
+        }
+        assert!(session_path(&dir, "missing").unwrap_err().starts_with("Session introuvable"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_search_dirs() {
+        let dir = sessions("search");
+        let first = [message("user", "Fix the Deploy script"), message("assistant", "deploy fixed")].join("\n");
+        fs::write(dir.join("s1.jsonl"), first).unwrap();
+        fs::write(dir.join("s2.jsonl"), message("user", "Run deploy again")).unwrap();
+        let dirs = vec![dir.clone()];
+
+        let hits = search_dirs(&dirs, "deploy", 10);
+        let found: Vec<(&str, usize)> = hits.iter().map(|h| (h.session_id.as_str(), h.message_index)).collect();
+        assert_eq!(found, vec![("s1", 0), ("s1", 1), ("s2", 0)]);
+        assert!(hits.iter().all(|h| h.project_path.as_deref() == Some("/work/api")));
+        assert_eq!(hits[0].role, "user");
+
+        assert_eq!(search_dirs(&dirs, "deploy", 2).len(), 2);
+        assert!(search_dirs(&dirs, "deploy", 0).is_empty());
+        assert!(search_dirs(&dirs, "rollback", 10).is_empty());
+
+        // Limite nulle ou requête vide : aucun dossier n'est parcouru
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        assert!(runtime.block_on(search_sessions("deploy".into(), Vec::new(), Some(0))).unwrap().is_empty());
+        assert!(runtime.block_on(search_sessions("  ".into(), Vec::new(), None)).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
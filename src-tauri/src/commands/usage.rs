@@ -0,0 +1,324 @@
+use crate::models::command::Command;
+use crate::models::config::Config;
+use crate::models::skill::{Skill, SkillSource};
+use crate::utils::claude_state::transcripts;
+use crate::utils::usage::{UsageCache, UsageCounter, UsageStats};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::skills::list_skills;
+use super::slash_commands::list_commands;
+use super::transcripts::user_sessions_root;
+use super::CLAUDE_DIR;
+
+/// Utilisation dans les sessions lancées depuis un projet (ou un de ses sous-dossiers)
+#[derive(Debug, Serialize)]
+pub struct ProjectUsage {
+    pub project_path: String,
+    pub stats: UsageStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageReport {
+    pub projects: Vec<ProjectUsage>,
+    pub total: UsageStats,
+    pub unused_skills: Vec<String>, // Identifiants des skills jamais chargés
+}
+
+/// Analyse des transcriptions : skills chargés, commandes slash, outils et outils MCP,
+/// par projet et par jour. Sans projet fourni, toutes les sessions connues sont prises en compte.
+#[tauri::command]
+pub async fn usage_analytics(project_paths: Vec<String>) -> Result<UsageReport, String> {
+    let mut skills = list_skills(project_paths.clone()).await?;
+    let usage = usage_by_cwd().await?;
+    apply_skill_usage(&mut skills, &usage);
+    // Un lien de projet est un alias : ses chargements sont comptés sur le skill lié
+    let unused_skills = skills
+        .into_iter()
+        .filter(|s| s.usage_count == 0 && !is_link(Path::new(&s.path)))
+        .map(|s| s.id)
+        .collect();
+
+    let roots: Vec<PathBuf> = project_paths.iter().map(|p| canonical(Path::new(p))).collect();
+    let mut projects: BTreeMap<String, UsageStats> = BTreeMap::new();
+    for (cwd, stats) in &usage {
+        let project_path = if roots.is_empty() {
+            Some(cwd.clone())
+        } else {
+            roots
+                .iter()
+                .find(|root| Path::new(cwd).starts_with(root))
+                .map(|root| root.to_string_lossy().to_string())
+        };
+        if let Some(project_path) = project_path {
+            projects.entry(project_path).or_default().merge(stats);
+        }
+    }
+
+    let mut total = UsageStats::default();
+    for stats in projects.values() {
+        total.merge(stats);
+    }
+
+    #[cfg(debug_assertions)]
+    eprintln!("📊 Utilisation analysée: {} sessions, {} projets", total.sessions, projects.len());
+
+    Ok(UsageReport {
+        projects: projects
+            .into_iter()
+            .map(|(project_path, stats)| ProjectUsage { project_path, stats })
+            .collect(),
+        total,
+        unused_skills,
+    })
+}
+
+/// Utilisation de chaque skill listé, par identifiant
+///
+/// Appelé après `list_skills` : la relecture des transcriptions ne retarde pas le listing.
+#[tauri::command]
+pub async fn skill_usage(project_paths: Vec<String>) -> Result<BTreeMap<String, UsageCounter>, String> {
+    let mut skills = list_skills(project_paths).await?;
+    apply_skill_usage(&mut skills, &usage_by_cwd().await?);
+    Ok(skills
+        .into_iter()
+        .map(|s| (s.id, UsageCounter { count: s.usage_count, last_used: s.last_used }))
+        .collect())
+}
+
+/// Utilisation de chaque commande listée, par identifiant (voir `skill_usage`)
+#[tauri::command]
+pub async fn command_usage(project_paths: Vec<String>) -> Result<BTreeMap<String, UsageCounter>, String> {
+    let mut commands = list_commands(project_paths).await?;
+    apply_command_usage(&mut commands, &usage_by_cwd().await?);
+    Ok(commands
+        .into_iter()
+        .map(|c| (c.id, UsageCounter { count: c.usage_count, last_used: c.last_used }))
+        .collect())
+}
+
+/// Une seule mise à jour du cache à la fois : les appels concurrents (skills, commandes,
+/// analyse) attendent la première puis trouvent un cache à jour
+static USAGE_REFRESH: Mutex<()> = Mutex::new(());
+
+/// `load_usage` hors du runtime async : la relecture des transcriptions peut être longue
+pub async fn usage_by_cwd() -> Result<BTreeMap<String, UsageStats>, String> {
+    tokio::task::spawn_blocking(load_usage)
+        .await
+        .map_err(|e| format!("Erreur analyse des sessions: {}", e))
+}
+
+/// Statistiques par dossier de travail, à partir du cache mis à jour avec les sessions modifiées
+pub fn load_usage() -> BTreeMap<String, UsageStats> {
+    let _refresh = USAGE_REFRESH.lock().unwrap_or_else(|e| e.into_inner());
+    let (Ok(config), Ok(root)) = (Config::load(), user_sessions_root()) else {
+        return BTreeMap::new();
+    };
+    let cache_path = PathBuf::from(config.expanded_usage_cache_path());
+    let mut cache = UsageCache::load(&cache_path);
+
+    let files: Vec<PathBuf> = fs::read_dir(&root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .flat_map(|dir| transcripts(&dir))
+        .collect();
+
+    if cache.refresh(&files) > 0 {
+        if let Err(e) = cache.save(&cache_path) {
+            #[cfg(debug_assertions)]
+            eprintln!("⚠️ Usage cache not saved: {}", e);
+        }
+    }
+    cache.by_project()
+}
+
+/// Renseigne `usage_count` et `last_used` des skills
+pub fn apply_skill_usage(skills: &mut [Skill], usage: &BTreeMap<String, UsageStats>) {
+    let loadables: Vec<Loadable> = skills
+        .iter()
+        .map(|skill| {
+            let path = Path::new(&skill.path);
+            let folder = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let mut names = vec![skill.name.as_str()];
+            if folder != skill.name {
+                names.push(folder);
+            }
+            Loadable::new(&skill.source, path, names)
+        })
+        .collect();
+    let mut counters = attribute_usage(&loadables, usage, |stats| &stats.skills);
+    attribute_reads(&loadables, usage, &mut counters);
+    for (skill, counter) in skills.iter_mut().zip(counters) {
+        skill.usage_count = counter.count;
+        skill.last_used = counter.last_used;
+    }
+}
+
+pub fn apply_command_usage(commands: &mut [Command], usage: &BTreeMap<String, UsageStats>) {
+    let loadables: Vec<Loadable> = commands
+        .iter()
+        .map(|command| Loadable::new(&command.source, Path::new(&command.path), vec![&command.invocation]))
+        .collect();
+    let counters = attribute_usage(&loadables, usage, |stats| &stats.commands);
+    for (command, counter) in commands.iter_mut().zip(counters) {
+        command.usage_count = counter.count;
+        command.last_used = counter.last_used;
+    }
+}
+
+/// Skill ou commande listé, tel que Claude Code le résout par son nom
+struct Loadable<'a> {
+    names: Vec<&'a str>,
+    project_root: Option<PathBuf>, // Parent du dossier .claude contenant l'élément (None : global)
+    canonical: PathBuf,
+    is_link: bool,
+}
+
+impl<'a> Loadable<'a> {
+    fn new(source: &SkillSource, path: &Path, names: Vec<&'a str>) -> Self {
+        let project_root = match source {
+            SkillSource::Global => None,
+            SkillSource::Project => path
+                .ancestors()
+                .find(|p| p.file_name().and_then(|n| n.to_str()) == Some(CLAUDE_DIR))
+                .and_then(Path::parent)
+                .map(canonical),
+        };
+        Loadable { names, project_root, canonical: canonical(path), is_link: is_link(path) }
+    }
+}
+
+/// Attribue chaque chargement à l'élément réellement résolu dans le dossier de la session :
+/// l'élément du projet le plus proche masque un élément global de même nom, et un lien de
+/// projet vers un élément listé compte pour sa cible plutôt qu'en double
+fn attribute_usage(
+    items: &[Loadable],
+    usage: &BTreeMap<String, UsageStats>,
+    counters: impl Fn(&UsageStats) -> &BTreeMap<String, UsageCounter>,
+) -> Vec<UsageCounter> {
+    let mut totals = vec![UsageCounter::default(); items.len()];
+    for (cwd, stats) in usage {
+        let cwd = Path::new(cwd);
+        for (name, counter) in counters(stats) {
+            let resolved = items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.names.contains(&name.as_str()))
+                .filter(|(_, item)| item.project_root.as_ref().is_none_or(|root| cwd.starts_with(root)))
+                .max_by_key(|(_, item)| item.project_root.as_ref().map_or(0, |root| root.as_os_str().len()));
+            let Some((index, item)) = resolved else {
+                continue;
+            };
+            let owner = if item.is_link {
+                items
+                    .iter()
+                    .position(|other| !other.is_link && other.canonical == item.canonical)
+                    .unwrap_or(index)
+            } else {
+                index
+            };
+            totals[owner].merge(counter);
+        }
+    }
+    totals
+}
+
+/// Lectures directes d'un SKILL.md : comptées sur le skill dont le dossier est celui lu
+/// (le skill réel plutôt qu'un lien qui y mène)
+fn attribute_reads(items: &[Loadable], usage: &BTreeMap<String, UsageStats>, totals: &mut [UsageCounter]) {
+    for stats in usage.values() {
+        for (dir, counter) in &stats.skill_reads {
+            let read = canonical(Path::new(dir));
+            let owner = items
+                .iter()
+                .position(|item| !item.is_link && item.canonical == read)
+                .or_else(|| items.iter().position(|item| item.canonical == read));
+            if let Some(owner) = owner {
+                totals[owner].merge(counter);
+            }
+        }
+    }
+}
+
+fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).map(|m| m.is_symlink()).unwrap_or(false)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(dir: &Path) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "# Skill").unwrap();
+        dir.to_path_buf()
+    }
+
+    fn stats(skills: &[(&str, usize)], reads: &[(&Path, usize)]) -> UsageStats {
+        let counter = |count| UsageCounter { count, last_used: None };
+        UsageStats {
+            skills: skills.iter().map(|(name, n)| (name.to_string(), counter(*n))).collect(),
+            skill_reads: reads.iter().map(|(dir, n)| (dir.to_string_lossy().to_string(), counter(*n))).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_attribute_usage_resolves_like_claude_code() {
+        let root = std::env::temp_dir().join(format!("usage-attribution-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let project = root.join("api");
+        let other = root.join("web");
+        let project_skills = project.join(CLAUDE_DIR).join("skills");
+        fs::create_dir_all(&project_skills).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        let root = canonical(&root);
+        let (project, other) = (canonical(&project), canonical(&other));
+
+        let global_review = skill(&root.join("global/review"));
+        let global_pdf = skill(&root.join("global/pdf"));
+        let project_review = skill(&project_skills.join("review"));
+        let project_deploy = skill(&project_skills.join("deploy"));
+        let pdf_link = project_skills.join("pdf");
+        std::os::unix::fs::symlink(&global_pdf, &pdf_link).unwrap();
+
+        let items = vec![
+            Loadable::new(&SkillSource::Global, &global_review, vec!["review"]),
+            Loadable::new(&SkillSource::Global, &global_pdf, vec!["pdf"]),
+            Loadable::new(&SkillSource::Project, &project_review, vec!["review"]),
+            Loadable::new(&SkillSource::Project, &project_deploy, vec!["deploy"]),
+            Loadable::new(&SkillSource::Project, &pdf_link, vec!["pdf"]),
+        ];
+        let usage = BTreeMap::from([
+            // Session dans un sous-dossier du projet : le skill du projet masque le global
+            (
+                project.join("src").to_string_lossy().to_string(),
+                stats(&[("review", 2), ("pdf", 3)], &[(&pdf_link, 1)]),
+            ),
+            // Autre projet : seul le skill global est visible
+            (
+                other.to_string_lossy().to_string(),
+                stats(&[("review", 1), ("deploy", 4)], &[(&project_review, 5)]),
+            ),
+        ]);
+
+        let mut counts = attribute_usage(&items, &usage, |stats| &stats.skills);
+        attribute_reads(&items, &usage, &mut counts);
+        let counts: Vec<usize> = counts.iter().map(|c| c.count).collect();
+        // review global, pdf global (lien et lecture via le lien compris), review projet
+        // (lecture de son SKILL.md), deploy jamais résolu hors du projet, lien sans comptage propre
+        assert_eq!(counts, vec![1, 4, 7, 0, 0]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,32 +1,83 @@
+mod cli;
 mod commands;
 mod models;
 mod utils;
 
-use commands::{analysis, config, projects, settings, skills};
+pub use cli::run_cli;
+
+use commands::{
+    analysis, approvals, budget, config, hooks_audit, links, mcp_secrets, profiles, projects,
+    routing, search, security, settings, skill_files, skills, slash_commands, transcripts, usage,
+};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(search::SearchState::default())
         .invoke_handler(tauri::generate_handler![
             skills::list_skills,
-            skills::list_commands,
             skills::get_skill,
             skills::create_skill_symlink,
+            links::get_link_graph,
+            links::repair_link,
+            links::bulk_link_skills,
             skills::duplicate_skill,
             skills::delete_skill,
             skills::update_skill,
+            skills::list_skill_templates,
+            skills::create_skill,
+            skills::trust_skill,
+            skill_files::list_skill_files,
+            skill_files::read_skill_file,
+            skill_files::write_skill_file,
+            skill_files::write_skill_file_bytes,
+            skill_files::rename_skill_file,
+            skill_files::delete_skill_file,
+            skill_files::set_skill_file_executable,
+            slash_commands::list_commands,
+            slash_commands::get_command,
+            slash_commands::create_command,
+            slash_commands::update_command,
+            slash_commands::delete_command,
+            slash_commands::duplicate_command,
+            slash_commands::create_command_symlink,
+            slash_commands::move_command,
+            slash_commands::analyze_command_collisions,
+            slash_commands::preview_command,
             projects::scan_projects,
+            projects::init_project,
+            transcripts::list_sessions,
+            transcripts::stream_session,
+            transcripts::search_sessions,
+            usage::usage_analytics,
+            usage::skill_usage,
+            usage::command_usage,
+            approvals::review_project_configs,
+            approvals::approve_project_config,
+            approvals::quarantine_project_config,
             config::load_config,
             config::save_config,
             config::get_config_path,
+            profiles::apply_profile,
+            profiles::diff_profile,
+            profiles::remove_profile,
             settings::load_hooks,
             settings::save_hooks,
+            hooks_audit::audit_hooks,
             settings::load_mcp,
             settings::save_mcp,
+            mcp_secrets::externalize_mcp_secrets,
+            mcp_secrets::materialize_mcp_env,
             settings::load_plugins,
             settings::save_plugins,
             analysis::analyze_skill,
+            analysis::analyze_skill_collisions,
+            budget::context_budget,
+            search::build_search_index,
+            search::search_skills,
+            routing::simulate_skill_routing,
+            security::scan_secrets,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
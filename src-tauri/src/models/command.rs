@@ -0,0 +1,283 @@
+use crate::utils::markdown_parser::{parse_frontmatter_as, split_frontmatter};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::skill::SkillSource;
+
+/// Frontmatter d'une commande slash (`.claude/commands/**/*.md`)
+///
+/// Les clés YAML sont en kebab-case (`argument-hint`, `allowed-tools`...), les clés
+/// inconnues sont conservées dans `extra` pour être réécrites telles quelles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandFrontmatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(
+        default,
+        alias = "argument-hint",
+        deserialize_with = "hint_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub argument_hint: Option<String>,
+    #[serde(default, alias = "allowed-tools", deserialize_with = "string_or_list")]
+    pub allowed_tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, alias = "disable-model-invocation", skip_serializing_if = "Option::is_none")]
+    pub disable_model_invocation: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CommandFrontmatter {
+    /// Parse le frontmatter d'une commande ; si le YAML est invalide (ex. `argument-hint: [a] [b]`,
+    /// accepté par Claude Code), repli sur un parsing ligne par ligne `clé: valeur`
+    pub fn parse(content: &str) -> (Self, String) {
+        if let Ok(parsed) = parse_frontmatter_as::<Self>(content) {
+            return parsed;
+        }
+
+        let Some((yaml, markdown)) = split_frontmatter(content) else {
+            return (Self::default(), content.trim().to_string());
+        };
+
+        let mut map = serde_json::Map::new();
+        for line in yaml.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                let value = match value {
+                    "true" => serde_json::Value::Bool(true),
+                    "false" => serde_json::Value::Bool(false),
+                    _ => serde_json::Value::String(value.trim_matches(['"', '\'']).to_string()),
+                };
+                map.insert(key.trim().to_string(), value);
+            }
+        }
+
+        let frontmatter = serde_json::from_value(serde_json::Value::Object(map)).unwrap_or_default();
+        (frontmatter, markdown.to_string())
+    }
+
+    /// Sérialise le frontmatter au format attendu par Claude Code (clés kebab-case)
+    pub fn to_yaml(&self) -> anyhow::Result<String> {
+        let mut mapping = serde_yaml::Mapping::new();
+        let mut insert = |key: &str, value: serde_yaml::Value| {
+            mapping.insert(serde_yaml::Value::String(key.to_string()), value);
+        };
+
+        if let Some(description) = &self.description {
+            insert("description", description.clone().into());
+        }
+        if let Some(hint) = &self.argument_hint {
+            insert("argument-hint", hint.clone().into());
+        }
+        if !self.allowed_tools.is_empty() {
+            insert("allowed-tools", self.allowed_tools.join(", ").into());
+        }
+        if let Some(model) = &self.model {
+            insert("model", model.clone().into());
+        }
+        if let Some(disable) = self.disable_model_invocation {
+            insert("disable-model-invocation", disable.into());
+        }
+        for (key, value) in &self.extra {
+            insert(key, serde_yaml::to_value(value)?);
+        }
+
+        if mapping.is_empty() {
+            return Ok(String::new());
+        }
+        Ok(serde_yaml::to_string(&mapping)?)
+    }
+}
+
+/// `allowed-tools` accepte une liste YAML ou une chaîne séparée par des virgules
+fn string_or_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        String(String),
+        List(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(StringOrList::List(list)) => list,
+        Some(StringOrList::String(s)) => split_tool_list(&s),
+    })
+}
+
+/// `argument-hint: [message]` est lu par YAML comme une liste : on reconstruit la chaîne
+fn hint_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        String(String),
+        List(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        None => None,
+        Some(StringOrList::String(s)) => Some(s),
+        Some(StringOrList::List(list)) => {
+            Some(list.iter().map(|i| format!("[{}]", i)).collect::<Vec<_>>().join(" "))
+        }
+    })
+}
+
+/// Découpe `Bash(git add:*), Read` en respectant les parenthèses
+fn split_tool_list(value: &str) -> Vec<String> {
+    let mut tools = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                tools.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    tools.push(current.trim().to_string());
+
+    tools.into_iter().filter(|t| !t.is_empty()).collect()
+}
+
+/// Portée d'une commande telle que Claude Code l'affiche (`(user)` ou `(project)`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandScope {
+    User,
+    Project,
+}
+
+impl From<&SkillSource> for CommandScope {
+    fn from(source: &SkillSource) -> Self {
+        match source {
+            SkillSource::Global => CommandScope::User,
+            SkillSource::Project => CommandScope::Project,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Command {
+    pub id: String,              // Hash du path
+    pub name: String,            // Nom du fichier (sans .md)
+    pub namespace: Option<String>, // Sous-dossiers de .claude/commands, séparés par ':'
+    pub invocation: String,      // Invocation exacte, ex. "/frontend:component"
+    pub scope: CommandScope,
+    pub description: String,     // frontmatter "description" (vide si None)
+    pub path: String,            // Chemin absolu du fichier .md
+    pub source: SkillSource,     // Global ou Project
+    pub project_name: Option<String>,
+    pub content: String,         // Markdown sans frontmatter
+    pub last_modified: u64,
+    pub linked_projects: Vec<String>, // Projets qui utilisent cette commande (symlinks)
+    pub argument_hint: Option<String>,
+    pub allowed_tools: Vec<String>,
+    pub model: Option<String>,
+    pub disable_model_invocation: bool,
+    pub extra_frontmatter: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub usage_count: usize,      // Invocations relevées dans les sessions (renseigné par command_usage)
+    #[serde(default)]
+    pub last_used: Option<u64>,
+}
+
+impl Command {
+    /// Invocation slash d'une commande : `/nom` ou `/ns1:ns2:nom`
+    pub fn invocation_for(namespace: Option<&str>, name: &str) -> String {
+        match namespace {
+            Some(ns) => format!("/{}:{}", ns, name),
+            None => format!("/{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::slash_commands::{command_collisions, load_command};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("command-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_identity_from_path() {
+        let root = temp_dir("identity");
+        let commands = root.join("app/.claude/commands");
+        write(&commands.join("deploy.md"), "---\ndescription: Deploy\n---\nGo");
+        write(&commands.join("frontend/component.md"), "Create");
+        write(&commands.join("git/flow/release.md"), "Release");
+
+        let top = load_command(&commands.join("deploy.md"), SkillSource::Project, Some("app".into())).unwrap();
+        assert_eq!((top.name.as_str(), top.namespace.as_deref()), ("deploy", None));
+        assert_eq!(top.invocation, "/deploy");
+        assert_eq!(top.scope, CommandScope::Project);
+        assert_eq!(top.description, "Deploy");
+
+        let nested = load_command(&commands.join("frontend/component.md"), SkillSource::Global, None).unwrap();
+        assert_eq!(nested.namespace.as_deref(), Some("frontend"));
+        assert_eq!(nested.invocation, "/frontend:component");
+        assert_eq!(nested.scope, CommandScope::User);
+
+        // Chaque niveau de dossier ajoute un segment de namespace
+        let deep = load_command(&commands.join("git/flow/release.md"), SkillSource::Project, None).unwrap();
+        assert_eq!(deep.namespace.as_deref(), Some("git:flow"));
+        assert_eq!(deep.invocation, "/git:flow:release");
+
+        assert_eq!(Command::invocation_for(Some("a:b"), "c"), "/a:b:c");
+        assert_eq!(Command::invocation_for(None, "c"), "/c");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_collisions_between_user_and_project_commands() {
+        let root = temp_dir("collisions");
+        let user = root.join("home/.claude/commands");
+        let project = root.join("app");
+        let project_commands = project.join(".claude/commands");
+        write(&user.join("deploy.md"), "User deploy");
+        write(&user.join("git/commit.md"), "User commit");
+        write(&user.join("review.md"), "User review");
+        write(&project_commands.join("deploy.md"), "Project deploy");
+        write(&project_commands.join("commit.md"), "Not namespaced");
+        write(&project_commands.join("git/commit.md"), "Project commit");
+        // Lien vers la commande utilisateur elle-même : pas un conflit
+        std::os::unix::fs::symlink(user.join("review.md"), project_commands.join("review.md")).unwrap();
+
+        let collisions = command_collisions(&user, vec![project.to_string_lossy().to_string()]).unwrap();
+        let found: Vec<(&str, &str)> = collisions
+            .iter()
+            .map(|c| (c.invocation.as_str(), c.project_name.as_str()))
+            .collect();
+        assert_eq!(found, vec![("/deploy", "app"), ("/git:commit", "app")]);
+        assert_eq!(collisions[1].user_command_path, user.join("git/commit.md").to_string_lossy());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
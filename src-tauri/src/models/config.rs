@@ -1,4 +1,6 @@
+use super::profile::Profile;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,6 +8,73 @@ pub struct Config {
     pub global_skills_path: String,
     pub projects_base_path: String,
     pub custom_project_paths: Vec<String>,
+    #[serde(default = "default_templates_path")]
+    pub templates_path: String,
+    #[serde(default = "default_vault_path")]
+    pub vault_path: String,
+    #[serde(default = "default_env_file_path")]
+    pub env_file_path: String,
+    #[serde(default = "default_approvals_path")]
+    pub approvals_path: String,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    #[serde(default = "default_applied_profiles_path")]
+    pub applied_profiles_path: String,
+    #[serde(default = "default_scan_exclude_globs")]
+    pub scan_exclude_globs: Vec<String>,
+    #[serde(default)]
+    pub scan_depths: BTreeMap<String, usize>,   // Profondeur par dossier de base (sinon MAX_SCAN_DEPTH)
+    #[serde(default = "default_scan_cache_path")]
+    pub scan_cache_path: String,
+    #[serde(default = "default_true")]
+    pub discover_claude_projects: bool,   // Ajoute les projets de ~/.claude.json et ~/.claude/projects
+    #[serde(default = "default_usage_cache_path")]
+    pub usage_cache_path: String,
+    #[serde(default = "default_skill_provenance_path")]
+    pub skill_provenance_path: String,
+}
+
+fn default_templates_path() -> String {
+    String::from("~/.config/claude-manager/templates")
+}
+
+fn default_vault_path() -> String {
+    String::from("~/.config/claude-manager/secrets.vault")
+}
+
+fn default_env_file_path() -> String {
+    String::from("~/.config/claude-manager/mcp.env")
+}
+
+fn default_approvals_path() -> String {
+    String::from("~/.config/claude-manager/approvals.json")
+}
+
+fn default_applied_profiles_path() -> String {
+    String::from("~/.config/claude-manager/applied-profiles.json")
+}
+
+fn default_scan_exclude_globs() -> Vec<String> {
+    ["node_modules", "target", "dist", "build", ".venv", "venv", "__pycache__", "vendor", ".cache"]
+        .iter()
+        .map(|dir| format!("**/{}", dir))
+        .collect()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_scan_cache_path() -> String {
+    String::from("~/.config/claude-manager/scan-cache.json")
+}
+
+fn default_usage_cache_path() -> String {
+    String::from("~/.config/claude-manager/usage-cache.json")
+}
+
+fn default_skill_provenance_path() -> String {
+    String::from("~/.config/claude-manager/skill-provenance.json")
 }
 
 impl Default for Config {
@@ -14,6 +83,18 @@ impl Default for Config {
             global_skills_path: String::from("~/.claude/skills"),
             projects_base_path: String::from("~/Projects"),
             custom_project_paths: Vec::new(),
+            templates_path: default_templates_path(),
+            vault_path: default_vault_path(),
+            env_file_path: default_env_file_path(),
+            approvals_path: default_approvals_path(),
+            profiles: Vec::new(),
+            applied_profiles_path: default_applied_profiles_path(),
+            scan_exclude_globs: default_scan_exclude_globs(),
+            scan_depths: BTreeMap::new(),
+            scan_cache_path: default_scan_cache_path(),
+            discover_claude_projects: true,
+            usage_cache_path: default_usage_cache_path(),
+            skill_provenance_path: default_skill_provenance_path(),
         }
     }
 }
@@ -50,6 +131,56 @@ impl Config {
         Self::expand_tilde(&self.projects_base_path)
     }
 
+    /// Retourne le dossier des modèles de skills utilisateur avec ~ expansé
+    pub fn expanded_templates_path(&self) -> String {
+        Self::expand_tilde(&self.templates_path)
+    }
+
+    /// Retourne le chemin du coffre de secrets chiffré avec ~ expansé
+    pub fn expanded_vault_path(&self) -> String {
+        Self::expand_tilde(&self.vault_path)
+    }
+
+    /// Retourne le chemin du fichier .env des secrets MCP avec ~ expansé
+    pub fn expanded_env_file_path(&self) -> String {
+        Self::expand_tilde(&self.env_file_path)
+    }
+
+    /// Retourne le chemin du registre d'approbations des hooks et serveurs MCP avec ~ expansé
+    pub fn expanded_approvals_path(&self) -> String {
+        Self::expand_tilde(&self.approvals_path)
+    }
+
+    /// Retourne le chemin du registre des éléments ajoutés par les profils avec ~ expansé
+    pub fn expanded_applied_profiles_path(&self) -> String {
+        Self::expand_tilde(&self.applied_profiles_path)
+    }
+
+    /// Retourne le chemin du cache de parcours des projets avec ~ expansé
+    pub fn expanded_scan_cache_path(&self) -> String {
+        Self::expand_tilde(&self.scan_cache_path)
+    }
+
+    /// Retourne le chemin du cache d'utilisation des skills et commandes avec ~ expansé
+    pub fn expanded_usage_cache_path(&self) -> String {
+        Self::expand_tilde(&self.usage_cache_path)
+    }
+
+    /// Retourne le chemin du registre de provenance des skills avec ~ expansé
+    pub fn expanded_skill_provenance_path(&self) -> String {
+        Self::expand_tilde(&self.skill_provenance_path)
+    }
+
+    /// Profondeur de recherche configurée pour un dossier de base (clé telle que saisie ou expansée)
+    pub fn scan_depth_for(&self, base_dir: &str, default: usize) -> usize {
+        let expanded = Self::expand_tilde(base_dir);
+        self.scan_depths
+            .iter()
+            .find(|(key, _)| key.as_str() == base_dir || Self::expand_tilde(key) == expanded)
+            .map(|(_, depth)| *depth)
+            .unwrap_or(default)
+    }
+
     /// Retourne tous les chemins de projets avec ~ expansé
     pub fn expanded_custom_project_paths(&self) -> Vec<String> {
         self.custom_project_paths
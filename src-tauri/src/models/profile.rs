@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Ensemble nommé de skills, commandes, hooks, serveurs MCP et permissions à appliquer à un projet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub mode: ProfileMode,
+    #[serde(default)]
+    pub skills: Vec<String>,               // Dossiers de skills (chemins, ~ accepté)
+    #[serde(default)]
+    pub commands: Vec<String>,             // Fichiers de commandes sous ~/.claude/commands
+    #[serde(default)]
+    pub hooks: serde_json::Value,          // Même format que la clé "hooks" de settings.json
+    #[serde(default)]
+    pub mcp_servers: serde_json::Value,    // Même format que la clé "mcpServers" de .mcp.json
+    #[serde(default)]
+    pub permissions: ProfilePermissions,
+}
+
+/// Skills et commandes liés par symlink ou copiés dans le projet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileMode {
+    #[default]
+    Link,
+    Copy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfilePermissions {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// Éléments ajoutés par chaque profil, par projet : seuls ceux-ci sont retirés par remove_profile
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AppliedProfiles {
+    /// Chemin canonique du projet → nom du profil → (kind, name) des éléments ajoutés
+    #[serde(default)]
+    pub projects: BTreeMap<String, BTreeMap<String, BTreeSet<(String, String)>>>,
+}
+
+impl AppliedProfiles {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).context("Registre des profils appliqués illisible")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
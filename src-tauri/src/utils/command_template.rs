@@ -0,0 +1,268 @@
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::paths::looks_binary;
+
+/// Taille maximale d'un fichier `@` inliné dans l'aperçu
+pub const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Taille cumulée maximale des fichiers inlinés
+pub const MAX_TOTAL_BYTES: u64 = 1024 * 1024;
+
+/// Ligne bash du préambule (`!`cmd`` ou ligne commençant par `!`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BashLine {
+    pub line: usize,
+    pub command: String,
+    pub allowed: bool,
+    pub output: Option<String>,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub raw: String,
+}
+
+/// Référence `@fichier` et résultat de son inlining
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReference {
+    pub reference: String,
+    pub path: String,
+    pub status: String, // "inlined" | "missing" | "too_large" | "binary" | "outside_project"
+    pub bytes: u64,
+}
+
+fn arguments_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\$(ARGUMENTS|\d+)").expect("arguments regex"))
+}
+
+fn inline_bash_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"!`([^`\n]+)`").expect("inline bash regex"))
+}
+
+fn file_reference_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?m)(^|[\s(])@([^\s`'"()<>,;]+)"#).expect("file reference regex"))
+}
+
+/// Remplace `$ARGUMENTS` par la chaîne complète et `$1`..`$n` par les arguments positionnels
+/// (chaîne vide si l'argument n'est pas fourni)
+pub fn substitute_arguments(content: &str, raw_arguments: &str, arguments: &[String]) -> String {
+    arguments_regex()
+        .replace_all(content, |caps: &regex::Captures| {
+            let key = &caps[1];
+            if key == "ARGUMENTS" {
+                return raw_arguments.to_string();
+            }
+            key.parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .and_then(|n| arguments.get(n - 1))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// Extrait les commandes bash : `!`cmd`` n'importe où, ou ligne entière préfixée par `!`
+pub fn extract_bash_lines(content: &str, allowed_tools: &[String]) -> Vec<BashLine> {
+    let mut lines = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let mut push = |command: &str, raw: &str| {
+            let command = command.trim().to_string();
+            lines.push(BashLine {
+                line: index + 1,
+                allowed: is_bash_allowed(&command, allowed_tools),
+                command,
+                output: None,
+                exit_code: None,
+                error: None,
+                raw: raw.to_string(),
+            });
+        };
+
+        let inline: Vec<_> = inline_bash_regex().captures_iter(line).collect();
+        if !inline.is_empty() {
+            for caps in inline {
+                push(&caps[1], &caps[0]);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if let Some(command) = trimmed.strip_prefix('!') {
+            if !command.trim().is_empty() && !command.starts_with('[') {
+                push(command, trimmed);
+            }
+        }
+    }
+
+    lines
+}
+
+/// Vérifie qu'une commande bash est couverte par `allowed-tools`
+///
+/// `Bash` autorise tout, `Bash(git add:*)` et `Bash(npm run *)` autorisent un préfixe,
+/// `Bash(git status)` uniquement la commande exacte. Une commande composée (`&&`, `|`, `;`)
+/// doit être autorisée segment par segment ; substitutions et redirections exigent `Bash`.
+pub fn is_bash_allowed(command: &str, allowed_tools: &[String]) -> bool {
+    if allowed_tools.iter().any(|t| t.trim() == "Bash") {
+        return true;
+    }
+    if ["$(", "`", ">", "<"].iter().any(|op| command.contains(op)) {
+        return false;
+    }
+
+    command
+        .split("&&")
+        .flat_map(|s| s.split("||"))
+        .flat_map(|s| s.split([';', '|', '&']))
+        .map(str::trim)
+        .all(|segment| !segment.is_empty() && allowed_tools.iter().any(|tool| tool_allows(tool, segment)))
+}
+
+fn tool_allows(tool: &str, command: &str) -> bool {
+    let Some(pattern) = tool.trim().strip_prefix("Bash(").and_then(|t| t.strip_suffix(')')) else {
+        return false;
+    };
+    let pattern = pattern.trim();
+
+    match pattern.strip_suffix(":*").or_else(|| pattern.strip_suffix('*')) {
+        Some(prefix) => {
+            let prefix = prefix.trim_end();
+            command == prefix || command.starts_with(&format!("{} ", prefix))
+        }
+        None => command == pattern,
+    }
+}
+
+/// Remplace chaque `@chemin` par le contenu du fichier (relatif au projet), dans les limites de taille
+pub fn inline_file_references(content: &str, project_dir: &Path) -> (String, Vec<FileReference>) {
+    let project_root = fs::canonicalize(project_dir).unwrap_or_else(|_| project_dir.to_path_buf());
+    let mut references = Vec::new();
+    let mut total: u64 = 0;
+
+    let rendered = file_reference_regex().replace_all(content, |caps: &regex::Captures| {
+        let prefix = &caps[1];
+        let reference = caps[2].trim_end_matches(['.', ':', '!', '?']);
+        let trailing = &caps[2][reference.len()..];
+        let original = format!("{}@{}{}", prefix, reference, trailing);
+
+        let candidate = project_root.join(reference);
+        let (status, bytes, inlined) = match fs::canonicalize(&candidate) {
+            Err(_) => ("missing", 0, None),
+            Ok(path) if !path.starts_with(&project_root) => ("outside_project", 0, None),
+            Ok(path) if path.is_dir() => ("missing", 0, None),
+            Ok(path) => {
+                let bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                if bytes > MAX_FILE_BYTES || total + bytes > MAX_TOTAL_BYTES {
+                    ("too_large", bytes, None)
+                } else {
+                    match fs::read(&path) {
+                        Ok(data) if looks_binary(&data) => ("binary", bytes, None),
+                        Ok(data) => {
+                            total += bytes;
+                            ("inlined", bytes, Some(String::from_utf8_lossy(&data).into_owned()))
+                        }
+                        Err(_) => ("missing", 0, None),
+                    }
+                }
+            }
+        };
+
+        references.push(FileReference {
+            reference: format!("@{}", reference),
+            path: candidate.to_string_lossy().to_string(),
+            status: status.to_string(),
+            bytes,
+        });
+
+        match inlined {
+            Some(text) => format!(
+                "{}\n<file path=\"{}\">\n{}\n</file>\n{}",
+                prefix,
+                reference,
+                text.trim_end(),
+                trailing
+            ),
+            None => original,
+        }
+    });
+
+    (rendered.into_owned(), references)
+}
+
+/// Remplace les commandes bash exécutées par leur sortie dans le prompt
+pub fn apply_bash_outputs(content: &str, bash_lines: &[BashLine]) -> String {
+    let mut rendered = content.to_string();
+    for line in bash_lines {
+        if let Some(output) = &line.output {
+            rendered = rendered.replacen(&line.raw, output.trim_end(), 1);
+        }
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_substitute_arguments() {
+        let args = vec!["123".to_string(), "high".to_string()];
+        let out = substitute_arguments("Fix #$1 ($2, $3): $ARGUMENTS", "123 high", &args);
+        assert_eq!(out, "Fix #123 (high, ): 123 high");
+    }
+
+    #[test]
+    fn test_bash_permissions() {
+        let allowed = tools(&["Bash(git add:*)", "Bash(git status)", "Read"]);
+        assert!(is_bash_allowed("git add .", &allowed));
+        assert!(is_bash_allowed("git status", &allowed));
+        assert!(!is_bash_allowed("git status --short", &allowed));
+        assert!(!is_bash_allowed("git addx", &allowed));
+        assert!(!is_bash_allowed("rm -rf /", &allowed));
+        assert!(is_bash_allowed("rm -rf /", &tools(&["Bash"])));
+        assert!(!is_bash_allowed("ls", &[]));
+        assert!(!is_bash_allowed("git status && rm -rf ~", &allowed));
+        assert!(is_bash_allowed("git add . && git status", &allowed));
+        assert!(!is_bash_allowed("git add $(cat files)", &allowed));
+    }
+
+    #[test]
+    fn test_extract_bash_lines() {
+        let content = "## Context\n\n- Status: !`git status`\n- Diff: !`git diff HEAD`\n! npm test\nNot bash!\n";
+        let lines = extract_bash_lines(content, &tools(&["Bash(git status:*)"]));
+        let commands: Vec<(&str, bool)> = lines.iter().map(|l| (l.command.as_str(), l.allowed)).collect();
+        assert_eq!(
+            commands,
+            vec![("git status", true), ("git diff HEAD", false), ("npm test", false)]
+        );
+        assert_eq!(lines[0].line, 3);
+    }
+
+    #[test]
+    fn test_inline_file_references() {
+        let dir = std::env::temp_dir().join(format!("command-template-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("logo.png"), [0x89, 0x50, 0x00, 0x01]).unwrap();
+
+        let (rendered, refs) =
+            inline_file_references("Review @src/main.rs. Also @logo.png and @missing.txt, user@example.com", &dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(rendered.contains("<file path=\"src/main.rs\">\nfn main() {}\n</file>"));
+        let statuses: Vec<&str> = refs.iter().map(|r| r.status.as_str()).collect();
+        assert_eq!(statuses, vec!["inlined", "binary", "missing"]);
+        assert!(rendered.contains("user@example.com"));
+    }
+}
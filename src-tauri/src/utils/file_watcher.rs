@@ -4,18 +4,25 @@ use std::sync::mpsc::channel;
 use tauri::{AppHandle, Emitter};
 
 /// Démarre le file watcher pour surveiller les modifications des fichiers skills
-pub fn start_watcher(
+///
+/// `on_change` est appelé avec les chemins modifiés avant l'émission de l'événement
+/// `skill-file-changed` vers le frontend.
+pub fn start_watcher<F>(
     paths: Vec<PathBuf>,
     app_handle: AppHandle,
-) -> Result<RecommendedWatcher, notify::Error> {
+    on_change: F,
+) -> Result<RecommendedWatcher, notify::Error>
+where
+    F: Fn(&AppHandle, &[PathBuf]) + Send + 'static,
+{
     let (tx, rx) = channel();
 
     let mut watcher = RecommendedWatcher::new(
         move |res: Result<Event, notify::Error>| {
             if let Ok(event) = res {
-                // Filtrer uniquement les modifications et suppressions
+                // Filtrer uniquement les créations, modifications et suppressions
                 match event.kind {
-                    EventKind::Modify(_) | EventKind::Remove(_) => {
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
                         let _ = tx.send(event);
                     }
                     _ => {}
@@ -25,16 +32,18 @@ pub fn start_watcher(
         notify::Config::default(),
     )?;
 
-    // Watch tous les chemins fournis
+    // Watch tous les chemins fournis (récursif : SKILL.md est dans un sous-dossier)
     for path in paths {
         if path.exists() {
-            watcher.watch(&path, RecursiveMode::NonRecursive)?;
+            watcher.watch(&path, RecursiveMode::Recursive)?;
         }
     }
 
     // Spawn un thread pour écouter les événements et les envoyer au frontend
     std::thread::spawn(move || {
         while let Ok(event) = rx.recv() {
+            on_change(&app_handle, &event.paths);
+
             // Émettre l'événement vers le frontend
             let paths: Vec<String> = event
                 .paths
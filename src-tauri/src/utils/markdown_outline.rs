@@ -0,0 +1,281 @@
+use crate::utils::text::normalize;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use std::ops::Range;
+use std::path::Path;
+
+/// Titre Markdown avec sa ligne (à partir de 1)
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub text: String,
+    pub line: usize,
+}
+
+/// Problème de structure détecté dans le Markdown
+#[derive(Debug, Clone)]
+pub struct MarkdownFinding {
+    pub severity: &'static str,   // "error" | "warning" | "info"
+    pub code: &'static str,
+    pub message: String,
+    pub current_value: Option<String>,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Plan d'un document Markdown et problèmes structurels détectés
+#[derive(Debug, Default)]
+pub struct MarkdownOutline {
+    pub headings: Vec<Heading>,
+    pub findings: Vec<MarkdownFinding>,
+}
+
+impl MarkdownOutline {
+    /// Vrai si un titre (de n'importe quel niveau) commence par l'un des noms donnés
+    pub fn has_section(&self, names: &[&str]) -> bool {
+        self.headings.iter().any(|h| {
+            let text = normalize(h.text.trim());
+            names.iter().any(|name| text.starts_with(&normalize(name)))
+        })
+    }
+}
+
+/// Analyse un fichier Markdown (frontmatter YAML compris) via un vrai parseur CommonMark
+///
+/// `base_dir` sert à vérifier les liens relatifs (references/, scripts/...).
+pub fn analyze_markdown(content: &str, base_dir: &Path) -> MarkdownOutline {
+    let lines = LineIndex::new(content);
+    let mut outline = MarkdownOutline::default();
+
+    let options = Options::ENABLE_YAML_STYLE_METADATA_BLOCKS | Options::ENABLE_TABLES;
+    let events: Vec<(Event, Range<usize>)> = Parser::new_ext(content, options)
+        .into_offset_iter()
+        .collect();
+
+    // Titre courant : (niveau, ligne, colonne, contenu rencontré depuis)
+    let mut open_section: Option<(u8, usize, usize, bool)> = None;
+    let mut previous_level: Option<u8> = None;
+    let mut heading_text: Option<String> = None;
+
+    for (event, range) in &events {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let level = *level as u8;
+                let (line, column) = lines.position(range.start);
+
+                if let Some((open_level, open_line, open_column, has_content)) = open_section {
+                    if !has_content && level <= open_level {
+                        outline.findings.push(empty_section(open_line, open_column, &outline.headings));
+                    }
+                }
+                if let Some(previous) = previous_level {
+                    if level > previous + 1 {
+                        outline.findings.push(MarkdownFinding {
+                            severity: "info",
+                            code: "HEADING_LEVEL_JUMP",
+                            message: format!("Saut de niveau de titre (h{} → h{})", previous, level),
+                            current_value: None,
+                            line,
+                            column,
+                        });
+                    }
+                }
+
+                previous_level = Some(level);
+                open_section = Some((level, line, column, false));
+                heading_text = Some(String::new());
+                outline.headings.push(Heading { text: String::new(), line });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let (Some(text), Some(heading)) = (heading_text.take(), outline.headings.last_mut()) {
+                    heading.text = text;
+                }
+            }
+            Event::Text(text) | Event::Code(text) if heading_text.is_some() => {
+                if let Some(buffer) = heading_text.as_mut() {
+                    buffer.push_str(text);
+                }
+            }
+            Event::Start(Tag::MetadataBlock(_)) | Event::End(TagEnd::MetadataBlock(_)) => {}
+            Event::Text(_) if heading_text.is_none() && content[range.clone()].trim().is_empty() => {}
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_))) => {
+                mark_content(&mut open_section);
+                check_code_fence(content, range, &lines, &mut outline.findings);
+            }
+            Event::Start(Tag::Link { dest_url, .. }) | Event::Start(Tag::Image { dest_url, .. }) => {
+                mark_content(&mut open_section);
+                check_link(dest_url, base_dir, lines.position(range.start), &mut outline.findings);
+            }
+            _ => {
+                if heading_text.is_none() {
+                    mark_content(&mut open_section);
+                }
+            }
+        }
+    }
+
+    // Le dernier titre est vide s'il n'est suivi d'aucun contenu
+    if let Some((_, line, column, false)) = open_section {
+        outline.findings.push(empty_section(line, column, &outline.headings));
+    }
+
+    outline.findings.sort_by_key(|f| (f.line, f.column));
+    outline
+}
+
+fn mark_content(open_section: &mut Option<(u8, usize, usize, bool)>) {
+    if let Some(section) = open_section.as_mut() {
+        section.3 = true;
+    }
+}
+
+fn empty_section(line: usize, column: usize, headings: &[Heading]) -> MarkdownFinding {
+    let title = headings
+        .iter()
+        .find(|h| h.line == line)
+        .map(|h| h.text.clone());
+    MarkdownFinding {
+        severity: "info",
+        code: "EMPTY_SECTION",
+        message: "Section sans contenu".into(),
+        current_value: title,
+        line,
+        column,
+    }
+}
+
+/// Un bloc ``` non refermé avale tout le reste du document
+fn check_code_fence(
+    content: &str,
+    range: &Range<usize>,
+    lines: &LineIndex,
+    findings: &mut Vec<MarkdownFinding>,
+) {
+    let block = &content[range.clone()];
+    let opening = block.trim_start();
+    let fence_char = opening.chars().next().unwrap_or('`');
+    let fence_len = opening.chars().take_while(|c| *c == fence_char).count();
+
+    let closed = block.trim_end().lines().count() > 1
+        && block
+            .trim_end()
+            .lines()
+            .last()
+            .map(|last| {
+                let last = last.trim();
+                last.chars().take_while(|c| *c == fence_char).count() >= fence_len
+                    && last.trim_start_matches(fence_char).trim().is_empty()
+            })
+            .unwrap_or(false);
+
+    if !closed {
+        let (line, column) = lines.position(range.start);
+        findings.push(MarkdownFinding {
+            severity: "warning",
+            code: "UNCLOSED_CODE_FENCE",
+            message: "Bloc de code non refermé : la suite du fichier est traitée comme du code".into(),
+            current_value: opening.lines().next().map(String::from),
+            line,
+            column,
+        });
+    }
+}
+
+/// Vérifie qu'un lien relatif pointe vers un fichier existant du skill
+fn check_link(dest: &str, base_dir: &Path, position: (usize, usize), findings: &mut Vec<MarkdownFinding>) {
+    let is_external = dest.contains("://") || dest.starts_with("mailto:");
+    if dest.is_empty() || is_external || dest.starts_with('#') || dest.starts_with('/') {
+        return;
+    }
+
+    let target = dest
+        .split(['#', '?'])
+        .next()
+        .unwrap_or(dest)
+        .replace("%20", " ");
+    if target.is_empty() || base_dir.join(&target).exists() {
+        return;
+    }
+
+    findings.push(MarkdownFinding {
+        severity: "warning",
+        code: "BROKEN_LINK",
+        message: format!("Lien relatif cassé : {} n'existe pas dans le dossier du skill", target),
+        current_value: Some(dest.to_string()),
+        line: position.0,
+        column: position.1,
+    });
+}
+
+/// Conversion d'un offset (octets) en ligne/colonne (caractères), à partir de 1
+struct LineIndex<'a> {
+    content: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(content: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { content, starts }
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.starts.partition_point(|start| *start <= offset).max(1);
+        let line_start = self.starts[line - 1];
+        let column = self.content[line_start..offset].chars().count() + 1;
+        (line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(outline: &MarkdownOutline) -> Vec<&str> {
+        outline.findings.iter().map(|f| f.code).collect()
+    }
+
+    #[test]
+    fn test_sections_any_level_ignore_code_blocks() {
+        let content = "---\nname: x\n---\n\n# Instructions\n\nDo it.\n\n### Examples\n\nOne.\n\n```\n## Troubleshooting\n```\n";
+        let outline = analyze_markdown(content, Path::new("/nonexistent"));
+        assert!(outline.has_section(&["examples"]));
+        assert!(!outline.has_section(&["troubleshooting"]));
+        assert_eq!(outline.headings[0].line, 5);
+    }
+
+    #[test]
+    fn test_empty_sections_and_level_jumps() {
+        let content = "# Title\n\n## Empty\n\n## Filled\n\nText\n\n#### Deep\n\nMore\n\n## Last\n";
+        let outline = analyze_markdown(content, Path::new("/nonexistent"));
+        assert_eq!(codes(&outline), vec!["EMPTY_SECTION", "HEADING_LEVEL_JUMP", "EMPTY_SECTION"]);
+        assert_eq!(outline.findings[0].line, 3);
+        assert_eq!(outline.findings[2].current_value.as_deref(), Some("Last"));
+    }
+
+    #[test]
+    fn test_unclosed_fence() {
+        let content = "# Title\n\n```bash\necho hi\n\n## Examples\n";
+        let outline = analyze_markdown(content, Path::new("/nonexistent"));
+        assert!(codes(&outline).contains(&"UNCLOSED_CODE_FENCE"));
+        assert!(!outline.has_section(&["examples"]));
+
+        let closed = analyze_markdown("# T\n\n```\ncode\n```\n", Path::new("/nonexistent"));
+        assert!(!codes(&closed).contains(&"UNCLOSED_CODE_FENCE"));
+    }
+
+    #[test]
+    fn test_broken_relative_links() {
+        let dir = std::env::temp_dir().join(format!("outline-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("references")).unwrap();
+        std::fs::write(dir.join("references/api.md"), "api").unwrap();
+
+        let content = "# T\n\nSee [api](references/api.md#auth), [gone](scripts/missing.sh) and [web](https://x.y).\n";
+        let outline = analyze_markdown(content, &dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(codes(&outline), vec!["BROKEN_LINK"]);
+        assert_eq!(outline.findings[0].current_value.as_deref(), Some("scripts/missing.sh"));
+        assert_eq!((outline.findings[0].line, outline.findings[0].column), (3, 36));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::models::skill::SkillFrontmatter;
 use anyhow::{Context, Result};
 use regex::Regex;
+use serde::de::DeserializeOwned;
 
 /// Parse le frontmatter YAML d'un fichier Markdown
 ///
@@ -47,6 +48,27 @@ pub fn parse_frontmatter(content: &str) -> Result<(SkillFrontmatter, String)> {
     }
 }
 
+/// Sépare le frontmatter YAML brut du contenu Markdown (None si pas de frontmatter)
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let content = content.trim();
+    let re = Regex::new(r"(?s)^---\s*\n(.*?)\n?---\s*\n(.*)$").ok()?;
+    let caps = re.captures(content)?;
+    Some((caps.get(1)?.as_str(), caps.get(2)?.as_str()))
+}
+
+/// Parse le frontmatter YAML dans un type arbitraire (valeur par défaut si absent)
+pub fn parse_frontmatter_as<T: DeserializeOwned + Default>(content: &str) -> Result<(T, String)> {
+    match split_frontmatter(content) {
+        // Un frontmatter vide (---\n---) est désérialisé comme null
+        Some((yaml_str, markdown)) if yaml_str.trim().is_empty() => Ok((T::default(), markdown.to_string())),
+        Some((yaml_str, markdown)) => {
+            let frontmatter = serde_yaml::from_str(yaml_str).context("Failed to parse YAML frontmatter")?;
+            Ok((frontmatter, markdown.to_string()))
+        }
+        None => Ok((T::default(), content.trim().to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +96,33 @@ description: Test description
         assert_eq!(markdown, content);
     }
 
+    #[test]
+    fn test_parse_command_frontmatter() {
+        use crate::models::command::CommandFrontmatter;
+
+        let content = r#"---
+description: Create a commit
+argument-hint: [message] [priority]
+allowed-tools: Bash(git add:*), Bash(git commit:*), Read
+disable-model-invocation: true
+custom-key: kept
+---
+
+Commit with $ARGUMENTS"#;
+        // `[message] [priority]` n'est pas du YAML valide : repli sur un parsing clé/valeur
+        assert!(parse_frontmatter_as::<CommandFrontmatter>(content).is_err());
+        let (frontmatter, markdown) = CommandFrontmatter::parse(content);
+        assert_eq!(frontmatter.argument_hint.as_deref(), Some("[message] [priority]"));
+        assert_eq!(frontmatter.allowed_tools, vec!["Bash(git add:*)", "Bash(git commit:*)", "Read"]);
+        assert_eq!(frontmatter.disable_model_invocation, Some(true));
+        assert_eq!(frontmatter.extra.get("custom-key").and_then(|v| v.as_str()), Some("kept"));
+        assert_eq!(markdown.trim(), "Commit with $ARGUMENTS");
+
+        let yaml = frontmatter.to_yaml().unwrap();
+        assert!(yaml.contains("allowed-tools: Bash(git add:*), Bash(git commit:*), Read"));
+        assert!(yaml.contains("custom-key: kept"));
+    }
+
     #[test]
     fn test_parse_no_description() {
         let content = r#"---
@@ -1,2 +1,23 @@
+pub mod approvals;
+pub mod claude_state;
+pub mod command_template;
 pub mod file_watcher;
+pub mod hook_audit;
+pub mod injection_scanner;
+pub mod markdown_outline;
 pub mod markdown_parser;
+pub mod paths;
+pub mod project_inventory;
+pub mod project_scanner;
+pub mod project_stack;
+pub mod script_checks;
+pub mod search_index;
+pub mod secret_scanner;
+pub mod skill_templates;
+pub mod symlinks;
+pub mod text;
+pub mod tokens;
+pub mod transcripts;
+pub mod triggers;
+pub mod usage;
+pub mod vault;
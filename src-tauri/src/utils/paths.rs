@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::skill_templates::STRUCTURE_FOLDERS;
+
+/// Résout un chemin relatif à l'intérieur de `base` en refusant toute sortie du dossier
+///
+/// Les chemins absolus et les `..` sont rejetés, puis l'ancêtre existant le plus proche (lien
+/// compris) est canonicalisé pour qu'un symlink ne permette pas de s'échapper ; les liens cassés
+/// sont refusés.
+pub fn safe_join(base: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative_path = Path::new(relative);
+    if relative.trim().is_empty() {
+        return Err("Chemin vide".to_string());
+    }
+    if relative_path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!("Chemin non autorisé: {}", relative));
+    }
+
+    let base = fs::canonicalize(base).map_err(|e| format!("Erreur résolution dossier: {}", e))?;
+    let joined = base.join(relative_path);
+
+    // `exists()` suit les symlinks : un lien cassé serait ignoré et l'écriture suivrait sa cible
+    let existing = joined
+        .ancestors()
+        .find(|p| fs::symlink_metadata(p).is_ok())
+        .ok_or_else(|| format!("Chemin non autorisé: {}", relative))?;
+    let resolved = match fs::canonicalize(existing) {
+        Ok(resolved) => resolved,
+        Err(_) if fs::symlink_metadata(existing).is_ok_and(|m| m.is_symlink()) => {
+            return Err(format!("Lien symbolique cassé: {}", relative));
+        }
+        Err(e) => return Err(format!("Erreur résolution chemin: {}", e)),
+    };
+    if !resolved.starts_with(&base) {
+        return Err(format!("Chemin hors du dossier: {}", relative));
+    }
+
+    Ok(joined)
+}
+
+/// Vérifie que le chemin relatif se trouve sous scripts/, references/ ou assets/
+pub fn ensure_structure_path(relative: &str) -> Result<(), String> {
+    let first = Path::new(relative)
+        .components()
+        .find_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    if STRUCTURE_FOLDERS.contains(&first) && Path::new(relative).components().count() > 1 {
+        Ok(())
+    } else {
+        Err(format!(
+            "Seuls les fichiers de scripts/, references/ et assets/ sont modifiables: {}",
+            relative
+        ))
+    }
+}
+
+/// Chemin relatif avec des `/`, quel que soit l'OS
+pub fn relative_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Heuristique binaire : octet nul dans les 8 premiers Ko ou contenu non UTF-8
+pub fn looks_binary(data: &[u8]) -> bool {
+    let head = &data[..data.len().min(8192)];
+    if head.contains(&0) {
+        return true;
+    }
+    // Un caractère multi-octets peut être coupé en fin de tampon
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Copie récursive d'un dossier (les symlinks internes sont suivis)
+pub fn copy_dir(source: &Path, destination: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Vrai si deux dossiers contiennent les mêmes fichiers avec le même contenu
+pub fn same_tree(a: &Path, b: &Path) -> bool {
+    let files = |root: &Path| -> Option<Vec<(String, Vec<u8>)>> {
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(root).follow_links(true).sort_by_file_name() {
+            let entry = entry.ok()?;
+            if entry.file_type().is_file() {
+                files.push((relative_path(root, entry.path()), fs::read(entry.path()).ok()?));
+            }
+        }
+        Some(files)
+    };
+    match (files(a), files(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_join_rejects_traversal() {
+        let dir = std::env::temp_dir().join(format!("paths-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::os::unix::fs::symlink("/tmp", dir.join("scripts/escape")).unwrap();
+        let outside = std::env::temp_dir().join(format!("paths-test-outside-{}", std::process::id()));
+        std::os::unix::fs::symlink(&outside, dir.join("scripts/dangling")).unwrap();
+
+        assert!(safe_join(&dir, "scripts/new/run.sh").is_ok());
+        assert!(safe_join(&dir, "../outside").is_err());
+        assert!(safe_join(&dir, "scripts/../../x").is_err());
+        assert!(safe_join(&dir, "/etc/passwd").is_err());
+        assert!(safe_join(&dir, "scripts/escape/file").is_err());
+        assert!(safe_join(&dir, "scripts/dangling").is_err());
+        assert!(safe_join(&dir, "scripts/dangling/file").is_err());
+        assert!(!outside.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_structure_paths() {
+        assert!(ensure_structure_path("scripts/run.sh").is_ok());
+        assert!(ensure_structure_path("references/api/auth.md").is_ok());
+        assert!(ensure_structure_path("SKILL.md").is_err());
+        assert!(ensure_structure_path("scripts").is_err());
+        assert!(ensure_structure_path("other/file.txt").is_err());
+    }
+
+    #[test]
+    fn test_copy_dir_and_compare() {
+        let dir = std::env::temp_dir().join(format!("paths-copy-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/scripts")).unwrap();
+        fs::write(dir.join("src/SKILL.md"), "# Skill").unwrap();
+        fs::write(dir.join("src/scripts/run.sh"), "echo ok").unwrap();
+
+        copy_dir(&dir.join("src"), &dir.join("copy")).unwrap();
+        assert!(same_tree(&dir.join("src"), &dir.join("copy")));
+        fs::write(dir.join("copy/scripts/run.sh"), "echo changed").unwrap();
+        assert!(!same_tree(&dir.join("src"), &dir.join("copy")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(!looks_binary("# Titre\néléphant".as_bytes()));
+        assert!(looks_binary(&[0x89, b'P', b'N', b'G', 0x00]));
+        assert!(looks_binary(&[0xff, 0xfe, b'a', b'b']));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::commands::{AGENTS_DIR, CLAUDE_DIR, COMMANDS_DIR, SKILLS_DIR};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Contenu Claude d'un projet et état git
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectInventory {
+    pub own_skills: usize,
+    pub linked_skills: usize,
+    pub commands: usize,
+    pub agents: usize,
+    pub hooks: usize,              // Commandes de hooks (settings.json + settings.local.json)
+    pub mcp_servers: usize,        // Serveurs de .mcp.json
+    pub permission_rules: usize,   // Règles allow / ask / deny
+    pub has_claude_md: bool,
+    pub has_claude_local_md: bool,
+    pub git_branch: Option<String>,
+    pub git_dirty: Option<bool>,
+}
+
+/// Inventaire complet d'un projet : chaque fichier n'est lu qu'une fois
+pub fn project_inventory(root: &Path) -> ProjectInventory {
+    let claude_dir = root.join(CLAUDE_DIR);
+    let mut inventory = ProjectInventory {
+        has_claude_md: root.join("CLAUDE.md").is_file() || claude_dir.join("CLAUDE.md").is_file(),
+        has_claude_local_md: root.join("CLAUDE.local.md").is_file(),
+        ..Default::default()
+    };
+
+    // Un skill est un dossier (ou un lien vers un dossier) contenant SKILL.md
+    let skills = fs::read_dir(claude_dir.join(SKILLS_DIR)).into_iter().flatten();
+    for entry in skills.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.join("SKILL.md").is_file() {
+            continue;
+        }
+        if entry.file_type().map(|t| t.is_symlink()).unwrap_or(false) {
+            inventory.linked_skills += 1;
+        } else {
+            inventory.own_skills += 1;
+        }
+    }
+
+    inventory.commands = count_markdown(&claude_dir.join(COMMANDS_DIR), usize::MAX);
+    inventory.agents = count_markdown(&claude_dir.join(AGENTS_DIR), 1);
+
+    for name in ["settings.json", "settings.local.json"] {
+        let Some(settings) = read_json(&claude_dir.join(name)) else {
+            continue;
+        };
+        inventory.hooks += settings
+            .get("hooks")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flat_map(|events| events.values())
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter_map(|group| group.get("hooks").and_then(Value::as_array))
+            .map(Vec::len)
+            .sum::<usize>();
+        inventory.permission_rules += ["allow", "ask", "deny"]
+            .iter()
+            .filter_map(|list| settings.get("permissions")?.get(list)?.as_array().map(Vec::len))
+            .sum::<usize>();
+    }
+
+    inventory.mcp_servers = read_json(&root.join(".mcp.json"))
+        .and_then(|mcp| mcp.get("mcpServers").and_then(Value::as_object).map(|s| s.len()))
+        .unwrap_or(0);
+
+    if let Some((branch, dirty)) = git_state(root) {
+        inventory.git_branch = branch;
+        inventory.git_dirty = Some(dirty);
+    }
+
+    inventory
+}
+
+fn count_markdown(dir: &Path, max_depth: usize) -> usize {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|x| x.to_str()) == Some("md"))
+        .count()
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Branche (None si HEAD détachée) et présence de modifications
+type GitState = (Option<String>, bool);
+
+/// Dates de modification de HEAD, de l'index (checkout, commit, `git add`) et du fichier ou
+/// dossier le plus récent de l'arbre de travail (édition, création, suppression)
+type GitStamp = (Option<SystemTime>, Option<SystemTime>, Option<SystemTime>);
+
+/// État git par dossier .git : `git status` n'est relancé que si l'empreinte a changé
+static GIT_STATES: Mutex<BTreeMap<PathBuf, (GitStamp, Option<GitState>)>> = Mutex::new(BTreeMap::new());
+
+/// Oublie les états git mémorisés (scan forcé) : le prochain listing relance `git status`
+pub fn forget_git_states() {
+    GIT_STATES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// État git du projet, en un seul appel à git
+fn git_state(root: &Path) -> Option<GitState> {
+    let (worktree, git_dir) = git_dir(root)?;
+    let modified = |name: &str| fs::metadata(git_dir.join(name)).and_then(|m| m.modified()).ok();
+    let stamp = (modified("HEAD"), modified("index"), newest_mtime(&worktree));
+
+    let mut states = GIT_STATES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached, state)) = states.get(&git_dir) {
+        if *cached == stamp {
+            return state.clone();
+        }
+    }
+    let state = run_git_status(root);
+    states.insert(git_dir, (stamp, state.clone()));
+    state
+}
+
+/// Arbre de travail et dossier .git du dépôt contenant `root` ; un fichier .git (worktree,
+/// sous-module) renvoie à son `gitdir`
+fn git_dir(root: &Path) -> Option<(PathBuf, PathBuf)> {
+    let dot_git = root.ancestors().map(|p| p.join(".git")).find(|p| p.exists())?;
+    let worktree = dot_git.parent()?.to_path_buf();
+    if dot_git.is_dir() {
+        return Some((worktree, dot_git));
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let target = content.lines().find_map(|l| l.strip_prefix("gitdir:"))?.trim();
+    let git_dir = worktree.join(target);
+    Some((worktree, git_dir))
+}
+
+/// Date de modification la plus récente de l'arbre de travail, hors .git et fichiers ignorés
+/// (ceux que `git status` ne signale pas)
+fn newest_mtime(worktree: &Path) -> Option<SystemTime> {
+    WalkBuilder::new(worktree)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+fn run_git_status(root: &Path) -> Option<GitState> {
+    let output = Command::new("git")
+        .args(["--no-optional-locks", "status", "--porcelain=v2", "--branch"])
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_git_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_git_status(status: &str) -> (Option<String>, bool) {
+    let branch = status
+        .lines()
+        .find_map(|l| l.strip_prefix("# branch.head "))
+        .filter(|b| *b != "(detached)")
+        .map(String::from);
+    let dirty = status.lines().any(|l| !l.starts_with('#') && !l.trim().is_empty());
+    (branch, dirty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inventory_counts() {
+        let root = std::env::temp_dir().join(format!("inventory-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let claude = root.join(".claude");
+        fs::create_dir_all(claude.join("skills/own")).unwrap();
+        fs::create_dir_all(claude.join("skills/empty")).unwrap();
+        fs::create_dir_all(root.join("shared/review")).unwrap();
+        fs::create_dir_all(claude.join("commands/git")).unwrap();
+        fs::create_dir_all(claude.join("agents")).unwrap();
+        fs::write(claude.join("skills/own/SKILL.md"), "# Own").unwrap();
+        fs::write(root.join("shared/review/SKILL.md"), "# Review").unwrap();
+        std::os::unix::fs::symlink(root.join("shared/review"), claude.join("skills/review")).unwrap();
+        fs::write(claude.join("commands/deploy.md"), "").unwrap();
+        fs::write(claude.join("commands/git/commit.md"), "").unwrap();
+        fs::write(claude.join("agents/reviewer.md"), "").unwrap();
+        fs::write(
+            claude.join("settings.json"),
+            r#"{"hooks":{"PreToolUse":[{"matcher":"Bash","hooks":[{"command":"a"},{"command":"b"}]}]},
+                "permissions":{"allow":["Bash(ls)"],"deny":["Read(.env)"]}}"#,
+        )
+        .unwrap();
+        fs::write(root.join(".mcp.json"), r#"{"mcpServers":{"db":{}}}"#).unwrap();
+        fs::write(root.join("CLAUDE.md"), "").unwrap();
+
+        let inventory = project_inventory(&root);
+        assert_eq!(
+            (inventory.own_skills, inventory.linked_skills, inventory.commands, inventory.agents),
+            (1, 1, 2, 1)
+        );
+        assert_eq!((inventory.hooks, inventory.permission_rules, inventory.mcp_servers), (2, 2, 1));
+        assert!(inventory.has_claude_md && !inventory.has_claude_local_md);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_git_status() {
+        let clean = "# branch.oid abc\n# branch.head main\n";
+        assert_eq!(parse_git_status(clean), (Some("main".into()), false));
+        let dirty = "# branch.head (detached)\n1 .M N... 100644 100644 100644 a b src/lib.rs\n";
+        assert_eq!(parse_git_status(dirty), (None, true));
+    }
+
+    #[test]
+    fn test_git_state_sees_edits_after_caching() {
+        let root = std::env::temp_dir().join(format!("inventory-git-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .is_ok_and(|o| o.status.success())
+        };
+        if !git(&["init", "-q", "-b", "main"]) {
+            return; // git absent
+        }
+        fs::write(root.join("README.md"), "v1").unwrap();
+        assert!(git(&["add", "."]) && git(&["commit", "-qm", "init"]));
+
+        assert_eq!(git_state(&root), Some((Some("main".into()), false)));
+        // Modification non indexée : ni HEAD ni l'index ne changent
+        let file = fs::OpenOptions::new().write(true).open(root.join("README.md")).unwrap();
+        file.set_len(0).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(git_state(&root), Some((Some("main".into()), true)));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
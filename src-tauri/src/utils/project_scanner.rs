@@ -0,0 +1,211 @@
+use crate::commands::CLAUDE_DIR;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Fichiers d'exclusion lus par le parcours : leur contenu change sans toucher au dossier
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Paramètres de parcours d'un dossier de base
+pub struct ScanOptions {
+    /// Profondeur maximale du dossier .claude sous la base (1 = la base elle-même est un projet)
+    pub max_depth: usize,
+    pub excludes: Vec<String>,
+}
+
+impl ScanOptions {
+    /// Empreinte des paramètres : un cache calculé avec d'autres paramètres est invalide
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.max_depth.to_le_bytes());
+        for glob in &self.excludes {
+            hasher.update(glob.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())[..16].to_string()
+    }
+}
+
+/// Résultat du parcours d'une base : racines de projets et date de modification des dossiers
+/// visités et des fichiers .gitignore / .ignore rencontrés
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachedScan {
+    pub fingerprint: String,
+    pub roots: Vec<String>,
+    pub dirs: BTreeMap<String, u64>,
+}
+
+impl CachedScan {
+    /// Un dossier ajouté ou supprimé modifie la date de son parent, une règle d'exclusion modifiée
+    /// celle de son fichier : si aucune date n'a changé, l'arborescence visitée est identique
+    pub fn is_fresh(&self, fingerprint: &str) -> bool {
+        self.fingerprint == fingerprint
+            && !self.dirs.is_empty()
+            && self.dirs.iter().all(|(path, mtime)| mtime_of(Path::new(path)) == Some(*mtime))
+    }
+}
+
+/// Cache persistant des parcours, par dossier de base
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    #[serde(default)]
+    pub bases: BTreeMap<String, CachedScan>,
+}
+
+impl ScanCache {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+fn mtime_of(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64)
+}
+
+fn build_excludes(globs: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob).with_context(|| format!("Motif d'exclusion invalide: {}", glob))?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Parcourt une base en parallèle à la recherche de dossiers contenant `.claude`
+///
+/// Les `.gitignore` et les motifs d'exclusion sont respectés, les symlinks sont suivis avec
+/// détection des boucles. `progress` reçoit le nombre de dossiers visités et de projets trouvés.
+pub fn scan_base(base: &Path, options: &ScanOptions, progress: &(dyn Fn(usize, usize) + Sync)) -> Result<CachedScan> {
+    let excludes = build_excludes(&options.excludes)?;
+    let roots = Mutex::new(Vec::new());
+    let dirs = Mutex::new(BTreeMap::new());
+    let visited = AtomicUsize::new(0);
+    let found = AtomicUsize::new(0);
+
+    // Le dossier .claude est cherché directement dans chaque dossier visité, même s'il est ignoré par git
+    let walker = WalkBuilder::new(base)
+        .hidden(false)
+        .follow_links(true)
+        .max_depth(Some(options.max_depth.saturating_sub(1)))
+        .filter_entry(move |entry| {
+            entry.file_name() != CLAUDE_DIR && entry.file_name() != ".git" && !excludes.is_match(entry.path())
+        })
+        .build_parallel();
+
+    walker.run(|| {
+        Box::new(|entry| {
+            let Ok(entry) = entry else {
+                // Boucle de symlinks, permission refusée... : la branche est ignorée
+                return WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|t| t.is_dir()) {
+                return WalkState::Continue;
+            }
+
+            let path = entry.path();
+            let watched = std::iter::once(path.to_path_buf()).chain(IGNORE_FILES.iter().map(|f| path.join(f)));
+            for watched in watched {
+                if let Some(mtime) = mtime_of(&watched) {
+                    dirs.lock().unwrap().insert(watched.to_string_lossy().to_string(), mtime);
+                }
+            }
+            if path.join(CLAUDE_DIR).is_dir() {
+                roots.lock().unwrap().push(path.to_string_lossy().to_string());
+                found.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let count = visited.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(200) {
+                progress(count, found.load(Ordering::Relaxed));
+            }
+            WalkState::Continue
+        })
+    });
+
+    let mut roots = roots.into_inner().unwrap();
+    roots.sort();
+    roots.dedup();
+    progress(visited.load(Ordering::Relaxed), roots.len());
+
+    Ok(CachedScan { fingerprint: options.fingerprint(), roots, dirs: dirs.into_inner().unwrap() })
+}
+
+/// Résout les chemins en doublons (même projet atteint par un symlink)
+pub fn unique_roots(roots: impl IntoIterator<Item = String>) -> Vec<PathBuf> {
+    let mut seen = std::collections::HashSet::new();
+    roots
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|root| seen.insert(root.canonicalize().unwrap_or_else(|_| root.clone())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(depth: usize) -> ScanOptions {
+        ScanOptions { max_depth: depth, excludes: vec!["**/node_modules".into()] }
+    }
+
+    #[test]
+    fn test_scan_respects_excludes_depth_and_gitignore() {
+        let base = std::env::temp_dir().join(format!("scanner-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        for dir in [
+            "app/.claude",
+            "app/node_modules/pkg/.claude",
+            "group/deep/nested/.claude",
+            "repo/.git",
+            "repo/.claude",
+            "repo/build/gen/.claude",
+        ] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+        }
+        fs::write(base.join("repo/.gitignore"), "build/\n").unwrap();
+        std::os::unix::fs::symlink(&base, base.join("app/loop")).unwrap();
+
+        let scan = scan_base(&base, &options(3), &|_, _| {}).unwrap();
+        let names: Vec<String> = scan
+            .roots
+            .iter()
+            .map(|r| Path::new(r).strip_prefix(&base).unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["app", "repo"]);
+        assert!(scan_base(&base, &options(4), &|_, _| {}).unwrap().roots.iter().any(|r| r.ends_with("nested")));
+
+        assert!(scan.is_fresh(&options(3).fingerprint()));
+        assert!(!scan.is_fresh(&options(4).fingerprint()));
+        fs::create_dir_all(base.join("new/.claude")).unwrap();
+        assert!(!scan.is_fresh(&options(3).fingerprint()));
+
+        // Une règle retirée du .gitignore invalide le cache sans changer la date du dossier
+        let scan = scan_base(&base, &options(3), &|_, _| {}).unwrap();
+        assert!(scan.is_fresh(&options(3).fingerprint()));
+        let gitignore = fs::OpenOptions::new().write(true).open(base.join("repo/.gitignore")).unwrap();
+        gitignore.set_len(0).unwrap();
+        gitignore.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60)).unwrap();
+        assert!(!scan.is_fresh(&options(3).fingerprint()));
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,217 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Commande usuelle du projet (build, test, lint...)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StackCommand {
+    pub label: String,
+    pub command: String,
+}
+
+/// Pile technique détectée à partir des manifestes à la racine du projet
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectStack {
+    pub name: Option<String>,
+    pub languages: Vec<String>,
+    pub frameworks: Vec<String>,
+    pub commands: Vec<StackCommand>,
+}
+
+impl ProjectStack {
+    fn command(&mut self, label: &str, command: &str) {
+        if !self.commands.iter().any(|c| c.command == command) {
+            self.commands.push(StackCommand { label: label.into(), command: command.into() });
+        }
+    }
+}
+
+/// Détecte Rust (Cargo.toml), JavaScript/TypeScript (package.json) et Python (pyproject.toml)
+pub fn detect_stack(project: &Path) -> ProjectStack {
+    let mut stack = ProjectStack::default();
+
+    if let Some(cargo) = read_toml(&project.join("Cargo.toml")) {
+        stack.languages.push("Rust".into());
+        stack.name = cargo.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str()).map(String::from);
+        let workspace = if cargo.get("workspace").is_some() { " --workspace" } else { "" };
+        stack.command("Build", &format!("cargo build{}", workspace));
+        stack.command("Test", &format!("cargo test{}", workspace));
+        stack.command("Lint", &format!("cargo clippy{} --all-targets -- -D warnings", workspace));
+        stack.command("Format", "cargo fmt");
+    }
+
+    if let Some(package) = fs::read_to_string(project.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+    {
+        detect_node(project, &package, &mut stack);
+    }
+
+    if let Some(pyproject) = read_toml(&project.join("pyproject.toml")) {
+        stack.languages.push("Python".into());
+        let name = pyproject
+            .get("project")
+            .or_else(|| pyproject.get("tool").and_then(|t| t.get("poetry")))
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str());
+        if stack.name.is_none() {
+            stack.name = name.map(String::from);
+        }
+
+        let runner = if project.join("uv.lock").exists() {
+            "uv run "
+        } else if pyproject.get("tool").and_then(|t| t.get("poetry")).is_some() {
+            "poetry run "
+        } else {
+            ""
+        };
+        let tools = pyproject.get("tool");
+        if tools.and_then(|t| t.get("pytest")).is_some() || project.join("tests").is_dir() {
+            stack.command("Test", &format!("{}pytest", runner));
+        }
+        if tools.and_then(|t| t.get("ruff")).is_some() {
+            stack.command("Lint", &format!("{}ruff check .", runner));
+            stack.command("Format", &format!("{}ruff format .", runner));
+        }
+        if tools.and_then(|t| t.get("mypy")).is_some() {
+            stack.command("Type check", &format!("{}mypy .", runner));
+        }
+    }
+
+    stack
+}
+
+fn detect_node(project: &Path, package: &Value, stack: &mut ProjectStack) {
+    let has_dependency = |name: &str| {
+        ["dependencies", "devDependencies"]
+            .iter()
+            .any(|key| package.get(key).and_then(|d| d.get(name)).is_some())
+    };
+
+    let typescript = has_dependency("typescript") || project.join("tsconfig.json").exists();
+    stack.languages.push(if typescript { "TypeScript" } else { "JavaScript" }.into());
+    if stack.name.is_none() {
+        stack.name = package.get("name").and_then(Value::as_str).map(String::from);
+    }
+    for (dependency, framework) in [
+        ("vue", "Vue"),
+        ("react", "React"),
+        ("svelte", "Svelte"),
+        ("next", "Next.js"),
+        ("nuxt", "Nuxt"),
+        ("vite", "Vite"),
+        ("@tauri-apps/api", "Tauri"),
+        ("express", "Express"),
+    ] {
+        if has_dependency(dependency) {
+            stack.frameworks.push(framework.into());
+        }
+    }
+
+    let manager = if project.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if project.join("yarn.lock").exists() {
+        "yarn"
+    } else if project.join("bun.lockb").exists() || project.join("bun.lock").exists() {
+        "bun"
+    } else {
+        "npm"
+    };
+    if let Some(scripts) = package.get("scripts").and_then(Value::as_object) {
+        for (script, label) in [
+            ("dev", "Dev server"),
+            ("build", "Build"),
+            ("test", "Test"),
+            ("lint", "Lint"),
+            ("format", "Format"),
+            ("typecheck", "Type check"),
+        ] {
+            if scripts.contains_key(script) {
+                stack.command(label, &format!("{} run {}", manager, script));
+            }
+        }
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()
+}
+
+/// Contenu de départ de CLAUDE.md décrivant la pile et les commandes du projet
+pub fn render_claude_md(project_name: &str, stack: &ProjectStack) -> String {
+    let mut out = format!("# {}\n\n", stack.name.as_deref().unwrap_or(project_name));
+    out.push_str("Project instructions for Claude. Keep this file short and up to date.\n\n");
+
+    out.push_str("## Stack\n\n");
+    if stack.languages.is_empty() {
+        out.push_str("- Describe the languages and frameworks used here\n");
+    }
+    for language in &stack.languages {
+        out.push_str(&format!("- {}\n", language));
+    }
+    for framework in &stack.frameworks {
+        out.push_str(&format!("- {}\n", framework));
+    }
+
+    out.push_str("\n## Commands\n\n");
+    if stack.commands.is_empty() {
+        out.push_str("- List the build, test and lint commands here\n");
+    }
+    for command in &stack.commands {
+        out.push_str(&format!("- {}: `{}`\n", command.label, command.command));
+    }
+
+    out.push_str("\n## Conventions\n\n- Describe code style, naming and testing expectations\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("stack-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_detect_rust_and_node() {
+        let dir = temp_project("mixed");
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"api\"\n\n[workspace]\nmembers = []\n").unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{"name":"web","scripts":{"build":"vite build","test":"vitest"},"devDependencies":{"typescript":"5","vue":"3"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("pnpm-lock.yaml"), "").unwrap();
+
+        let stack = detect_stack(&dir);
+        assert_eq!(stack.name.as_deref(), Some("api"));
+        assert_eq!(stack.languages, vec!["Rust", "TypeScript"]);
+        assert_eq!(stack.frameworks, vec!["Vue"]);
+        let commands: Vec<&str> = stack.commands.iter().map(|c| c.command.as_str()).collect();
+        assert!(commands.contains(&"cargo test --workspace"));
+        assert!(commands.contains(&"pnpm run build"));
+
+        let claude_md = render_claude_md("fallback", &stack);
+        assert!(claude_md.starts_with("# api\n"));
+        assert!(claude_md.contains("- Test: `cargo test --workspace`"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_python() {
+        let dir = temp_project("python");
+        fs::write(dir.join("pyproject.toml"), "[project]\nname = \"tool\"\n\n[tool.ruff]\nline-length = 100\n").unwrap();
+        fs::write(dir.join("uv.lock"), "").unwrap();
+
+        let stack = detect_stack(&dir);
+        assert_eq!(stack.name.as_deref(), Some("tool"));
+        assert_eq!(stack.commands[0].command, "uv run ruff check .");
+        assert!(detect_stack(&temp_project("empty")).languages.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,345 @@
+use regex::Regex;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+use super::paths::{looks_binary, relative_path};
+
+/// Extensions considérées comme des scripts exécutables
+const SCRIPT_EXTENSIONS: [&str; 10] = ["sh", "bash", "zsh", "py", "js", "mjs", "cjs", "ts", "rb", "pl"];
+
+/// Durée maximale d'une vérification de syntaxe : au-delà, l'interpréteur est arrêté
+const SYNTAX_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Problème détecté dans un fichier de scripts/
+#[derive(Debug, Clone)]
+pub struct ScriptFinding {
+    pub severity: &'static str,   // "error" | "warning" | "info"
+    pub code: &'static str,
+    pub message: String,
+    pub current_value: Option<String>,
+    pub file: String,             // Chemin relatif au dossier du skill
+    pub line: Option<usize>,
+}
+
+impl ScriptFinding {
+    /// Emplacement au format `scripts/run.sh:12`
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.file, line),
+            None => self.file.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Language {
+    Shell,
+    Python,
+    Node,
+    Other,
+}
+
+/// Chemin absolu propre à une machine (dossier personnel, /opt, /srv, /mnt, lecteur Windows) ;
+/// le groupe 1 contient le chemin, précédé d'un début de ligne ou d'un séparateur
+fn absolute_path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:^|[\s"'`=(:,\[])(/(?:Users|home|opt|srv|mnt|media)/[^/\s"'`]+|/root\b|[A-Z]:\\[^\s"'`]*)"#,
+        )
+        .expect("absolute path regex")
+    })
+}
+
+/// Inspecte chaque script de `scripts/` : shebang, bit exécutable, syntaxe (interpréteurs locaux),
+/// référence depuis SKILL.md et chemins absolus codés en dur
+pub fn check_scripts(skill_dir: &Path, skill_md: &str) -> Vec<ScriptFinding> {
+    let scripts_dir = skill_dir.join("scripts");
+    if !scripts_dir.is_dir() {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+
+    for entry in WalkDir::new(&scripts_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(data) = fs::read(path) else {
+            continue;
+        };
+        if looks_binary(&data) {
+            continue;
+        }
+
+        let content = String::from_utf8_lossy(&data);
+        let shebang = content.lines().next().filter(|l| l.starts_with("#!"));
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        let is_script = SCRIPT_EXTENSIONS.contains(&extension) || shebang.is_some();
+        if !is_script {
+            continue;
+        }
+
+        let file = relative_path(skill_dir, path);
+        let mut push = |severity, code, message: String, current_value: Option<String>, line| {
+            findings.push(ScriptFinding { severity, code, message, current_value, file: file.clone(), line });
+        };
+
+        if shebang.is_none() {
+            push(
+                "warning",
+                "SCRIPT_MISSING_SHEBANG",
+                "Script sans shebang (#!/usr/bin/env ...) : l'interpréteur n'est pas déterminé".into(),
+                content.lines().next().map(String::from),
+                Some(1),
+            );
+        }
+
+        let executable = fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false);
+        if !executable {
+            push(
+                "warning",
+                "SCRIPT_NOT_EXECUTABLE",
+                "Le script n'a pas le bit exécutable (chmod +x)".into(),
+                None,
+                None,
+            );
+        }
+
+        if let Some((line, message)) = syntax_error(path, language(extension, shebang)) {
+            push("error", "SCRIPT_SYNTAX_ERROR", "Erreur de syntaxe dans le script".into(), Some(message), line);
+        }
+
+        if !skill_md.contains(&file) && !skill_md.contains(name) {
+            push(
+                "info",
+                "SCRIPT_UNREFERENCED",
+                "Script jamais mentionné dans SKILL.md : Claude ne saura pas quand l'utiliser".into(),
+                None,
+                None,
+            );
+        }
+
+        for (index, line) in content.lines().enumerate() {
+            if let Some(m) = absolute_path_regex().captures(line).and_then(|c| c.get(1)) {
+                push(
+                    "warning",
+                    "SCRIPT_ABSOLUTE_PATH",
+                    "Chemin absolu codé en dur : le skill ne fonctionnera pas sur une autre machine".into(),
+                    Some(m.as_str().to_string()),
+                    Some(index + 1),
+                );
+            }
+        }
+    }
+
+    findings
+}
+
+fn language(extension: &str, shebang: Option<&str>) -> Language {
+    match extension {
+        "sh" | "bash" => return Language::Shell,
+        "py" => return Language::Python,
+        "js" | "mjs" | "cjs" => return Language::Node,
+        "" => {}
+        _ => return Language::Other,
+    }
+
+    let shebang = shebang.unwrap_or_default();
+    if shebang.contains("python") {
+        Language::Python
+    } else if shebang.contains("node") {
+        Language::Node
+    } else if shebang.ends_with("sh") || shebang.contains("bash") {
+        Language::Shell
+    } else {
+        Language::Other
+    }
+}
+
+/// Valide la syntaxe via l'interpréteur local ; None si valide, interpréteur absent ou timeout
+///
+/// Pour Python, `compile()` vérifie la même syntaxe que `py_compile` sans écrire de `__pycache__`.
+fn syntax_error(path: &Path, language: Language) -> Option<(Option<usize>, String)> {
+    let mut command = match language {
+        Language::Shell => Command::new("bash"),
+        Language::Python => Command::new("python3"),
+        Language::Node => Command::new("node"),
+        Language::Other => return None,
+    };
+    match language {
+        Language::Shell => command.arg("-n"),
+        Language::Python => command
+            .arg("-c")
+            .arg("import sys; compile(open(sys.argv[1], 'rb').read(), sys.argv[1], 'exec')"),
+        _ => command.arg("--check"),
+    };
+    command.arg(path);
+
+    let (success, stderr) = run_with_timeout(command, SYNTAX_CHECK_TIMEOUT)?;
+    if success {
+        return None;
+    }
+
+    let stderr = stderr.trim().to_string();
+    let line = error_line(&stderr, path);
+    let message = stderr
+        .lines()
+        .rev()
+        .find(|l| l.contains("rror") || l.contains("syntax"))
+        .or_else(|| stderr.lines().next())
+        .unwrap_or("Erreur de syntaxe")
+        .replace(&path.to_string_lossy().to_string(), "")
+        .trim_start_matches([':', ' '])
+        .to_string();
+
+    Some((line, message))
+}
+
+/// Exécute la commande sans stdin ni stdout ; (succès, stderr), None si lancement impossible
+/// ou si elle dépasse `timeout` (le processus est alors tué)
+fn run_with_timeout(mut command: Command, timeout: Duration) -> Option<(bool, String)> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    // Lecture dans un thread : une sortie d'erreur volumineuse ne bloque pas l'interpréteur
+    let mut stderr = child.stderr.take()?;
+    let reader = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stderr.read_to_end(&mut buffer);
+        buffer
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() < timeout => thread::sleep(Duration::from_millis(20)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                #[cfg(debug_assertions)]
+                eprintln!("⏱️ Vérification de syntaxe interrompue : {:?}", command.get_program());
+                return None;
+            }
+        }
+    };
+
+    let stderr = reader.join().ok()?;
+    Some((status.success(), String::from_utf8_lossy(&stderr).into_owned()))
+}
+
+/// Numéro de ligne rattaché au script dans la sortie d'erreur
+/// (bash `file: line 3:`, python `File "file", line 3`, node `file:3`)
+fn error_line(stderr: &str, path: &Path) -> Option<usize> {
+    let path = regex::escape(&path.to_string_lossy());
+    let re = Regex::new(&format!(r#"{}(?:", line |: line |:)(\d+)"#, path)).ok()?;
+    re.captures(stderr)
+        .and_then(|c| c.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(findings: &[ScriptFinding], file: &str) -> Vec<&'static str> {
+        findings.iter().filter(|f| f.file == file).map(|f| f.code).collect()
+    }
+
+    #[test]
+    fn test_check_scripts() {
+        let dir = std::env::temp_dir().join(format!("script-checks-{}", std::process::id()));
+        let scripts = dir.join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+
+        fs::write(scripts.join("ok.sh"), "#!/usr/bin/env bash\necho ok\n").unwrap();
+        fs::set_permissions(scripts.join("ok.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        fs::write(scripts.join("broken.sh"), "echo start\nif true; then\n  cat /Users/alice/data.csv\n").unwrap();
+        fs::write(scripts.join(".gitkeep"), "").unwrap();
+        fs::write(scripts.join("notes.txt"), "not a script").unwrap();
+
+        let findings = check_scripts(&dir, "Run `scripts/ok.sh` then broken.sh");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(codes(&findings, "scripts/ok.sh").is_empty());
+        let broken = codes(&findings, "scripts/broken.sh");
+        assert!(broken.contains(&"SCRIPT_MISSING_SHEBANG"));
+        assert!(broken.contains(&"SCRIPT_NOT_EXECUTABLE"));
+        assert!(broken.contains(&"SCRIPT_SYNTAX_ERROR"));
+        assert!(broken.contains(&"SCRIPT_ABSOLUTE_PATH"));
+        assert!(!broken.contains(&"SCRIPT_UNREFERENCED"));
+        assert!(codes(&findings, "scripts/notes.txt").is_empty());
+
+        let absolute = findings.iter().find(|f| f.code == "SCRIPT_ABSOLUTE_PATH").unwrap();
+        assert_eq!(absolute.location(), "scripts/broken.sh:3");
+    }
+
+    #[test]
+    fn test_unreferenced_and_python_syntax() {
+        let dir = std::env::temp_dir().join(format!("script-checks-py-{}", std::process::id()));
+        let scripts = dir.join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("tool.py"), "#!/usr/bin/env python3\ndef f(:\n    pass\n").unwrap();
+
+        let findings = check_scripts(&dir, "No scripts mentioned");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let tool = codes(&findings, "scripts/tool.py");
+        assert!(tool.contains(&"SCRIPT_UNREFERENCED"));
+        // La vérification de syntaxe n'a lieu que si python3 est installé
+        if Command::new("python3").arg("--version").output().is_ok() {
+            let error = findings.iter().find(|f| f.code == "SCRIPT_SYNTAX_ERROR").unwrap();
+            assert_eq!(error.line, Some(2));
+        }
+    }
+
+    #[test]
+    fn test_absolute_path_roots() {
+        let path = |line: &str| {
+            absolute_path_regex()
+                .captures(line)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().to_string())
+        };
+        assert_eq!(path("cat /Users/alice/data.csv").as_deref(), Some("/Users/alice"));
+        assert_eq!(path("cd /opt/tools && make").as_deref(), Some("/opt/tools"));
+        assert_eq!(path("DATA=/srv/data").as_deref(), Some("/srv/data"));
+        assert_eq!(path("ls /mnt/backup").as_deref(), Some("/mnt/backup"));
+        assert_eq!(path("source /root/.bashrc").as_deref(), Some("/root"));
+        assert_eq!(path(r#"open("D:\work\in.txt")"#).as_deref(), Some(r"D:\work\in.txt"));
+        assert_eq!(path("curl https://example.com/home/page"), None);
+        assert_eq!(path("cat ./opt/readme /rootfs"), None);
+    }
+
+    #[test]
+    fn test_syntax_check_times_out() {
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        let started = Instant::now();
+        assert_eq!(run_with_timeout(command, Duration::from_millis(100)), None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("echo oops >&2; exit 2");
+        assert_eq!(run_with_timeout(command, SYNTAX_CHECK_TIMEOUT), Some((false, "oops\n".to_string())));
+    }
+}
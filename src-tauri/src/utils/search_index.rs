@@ -0,0 +1,448 @@
+use crate::models::skill::SkillSource;
+use crate::utils::text::{edit_distance, tokenize, tokens_with_offsets};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Paramètres BM25 classiques
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+// Poids appliqués aux correspondances approximatives
+const PREFIX_WEIGHT: f64 = 0.7;
+const FUZZY_WEIGHT: f64 = 0.5;
+
+const SNIPPET_RADIUS: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchField {
+    Name,
+    Description,
+    Body,
+    References,
+}
+
+impl SearchField {
+    pub const ALL: [SearchField; 4] = [
+        SearchField::Name,
+        SearchField::Description,
+        SearchField::Body,
+        SearchField::References,
+    ];
+
+    /// Boost appliqué au score BM25 du champ
+    fn boost(self) -> f64 {
+        match self {
+            SearchField::Name => 3.0,
+            SearchField::Description => 2.0,
+            SearchField::Body => 1.0,
+            SearchField::References => 0.5,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentKind {
+    Skill,
+    Command,
+}
+
+/// Document indexable : un skill (SKILL.md + references/) ou une commande
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchDocument {
+    pub id: String,
+    pub kind: DocumentKind,
+    pub path: String,
+    pub source: SkillSource,
+    pub project_name: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub body: String,
+    pub references: String,
+}
+
+impl SearchDocument {
+    fn field(&self, field: SearchField) -> &str {
+        match field {
+            SearchField::Name => &self.name,
+            SearchField::Description => &self.description,
+            SearchField::Body => &self.body,
+            SearchField::References => &self.references,
+        }
+    }
+}
+
+/// Fragment d'extrait, surligné si le texte correspond à un terme recherché
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlight: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub id: String,
+    pub kind: DocumentKind,
+    pub path: String,
+    pub source: SkillSource,
+    pub project_name: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub score: f64,
+    pub matched_fields: Vec<SearchField>,
+    pub snippet: Vec<SnippetPart>,
+}
+
+struct IndexedDocument {
+    document: SearchDocument,
+    term_freqs: [HashMap<String, u32>; 4],
+    lengths: [usize; 4],
+}
+
+/// Index inversé en mémoire avec scoring BM25 par champ
+#[derive(Default)]
+pub struct SearchIndex {
+    documents: HashMap<String, IndexedDocument>,
+    postings: HashMap<String, HashSet<String>>,
+    total_lengths: [usize; 4],
+}
+
+impl SearchIndex {
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.postings.clear();
+        self.total_lengths = [0; 4];
+    }
+
+    pub fn documents(&self) -> impl Iterator<Item = &SearchDocument> {
+        self.documents.values().map(|d| &d.document)
+    }
+
+    /// Ajoute ou remplace un document (identifié par son id)
+    pub fn upsert(&mut self, document: SearchDocument) {
+        self.remove(&document.id);
+
+        let mut term_freqs: [HashMap<String, u32>; 4] = Default::default();
+        let mut lengths = [0usize; 4];
+
+        for field in SearchField::ALL {
+            let tokens = tokenize(document.field(field));
+            lengths[field.index()] = tokens.len();
+            self.total_lengths[field.index()] += tokens.len();
+            for token in tokens {
+                *term_freqs[field.index()].entry(token).or_insert(0) += 1;
+            }
+        }
+
+        for freqs in &term_freqs {
+            for term in freqs.keys() {
+                self.postings
+                    .entry(term.clone())
+                    .or_default()
+                    .insert(document.id.clone());
+            }
+        }
+
+        self.documents.insert(
+            document.id.clone(),
+            IndexedDocument {
+                document,
+                term_freqs,
+                lengths,
+            },
+        );
+    }
+
+    /// Retire un document de l'index, retourne true s'il était présent
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(indexed) = self.documents.remove(id) else {
+            return false;
+        };
+
+        for field in SearchField::ALL {
+            self.total_lengths[field.index()] -= indexed.lengths[field.index()];
+            for term in indexed.term_freqs[field.index()].keys() {
+                if let Some(ids) = self.postings.get_mut(term) {
+                    ids.remove(id);
+                    if ids.is_empty() {
+                        self.postings.remove(term);
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Recherche plein texte avec tolérance aux fautes de frappe
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_terms = tokenize(query);
+        if query_terms.is_empty() || self.documents.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<&str, f64> = HashMap::new();
+        let mut matched_terms: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+        for query_term in &query_terms {
+            // Meilleure contribution par document pour ce terme de requête
+            let mut best: HashMap<&str, (f64, &str)> = HashMap::new();
+
+            for (term, weight) in self.expand_term(query_term) {
+                let Some(ids) = self.postings.get(term) else {
+                    continue;
+                };
+                let idf = self.idf(ids.len());
+                for id in ids {
+                    let score = weight * idf * self.term_score(&self.documents[id], term);
+                    let entry = best.entry(id.as_str()).or_insert((0.0, term));
+                    if score > entry.0 {
+                        *entry = (score, term);
+                    }
+                }
+            }
+
+            for (id, (score, term)) in best {
+                *scores.entry(id).or_insert(0.0) += score;
+                matched_terms.entry(id).or_default().insert(term);
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .filter(|(_, score)| *score > 0.0)
+            .map(|(id, score)| {
+                let indexed = &self.documents[id];
+                let terms = &matched_terms[id];
+                self.build_hit(indexed, score, terms)
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Termes du vocabulaire correspondant à un terme de requête, avec leur poids
+    fn expand_term<'a>(&'a self, query_term: &str) -> Vec<(&'a str, f64)> {
+        let query_len = query_term.chars().count();
+        let max_edits = match query_len {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+
+        self.postings
+            .keys()
+            .filter_map(|term| {
+                if term == query_term {
+                    Some((term.as_str(), 1.0))
+                } else if query_len >= 3 && term.starts_with(query_term) {
+                    Some((term.as_str(), PREFIX_WEIGHT))
+                } else if max_edits > 0
+                    && term.chars().count().abs_diff(query_len) <= max_edits
+                    && edit_distance(term, query_term) <= max_edits
+                {
+                    Some((term.as_str(), FUZZY_WEIGHT))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn idf(&self, doc_freq: usize) -> f64 {
+        let n = self.documents.len() as f64;
+        let df = doc_freq as f64;
+        (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+    }
+
+    /// Somme des scores BM25 de chaque champ, pondérés par leur boost
+    fn term_score(&self, indexed: &IndexedDocument, term: &str) -> f64 {
+        let n = self.documents.len() as f64;
+        SearchField::ALL
+            .iter()
+            .map(|field| {
+                let i = field.index();
+                let tf = indexed.term_freqs[i].get(term).copied().unwrap_or(0) as f64;
+                if tf == 0.0 {
+                    return 0.0;
+                }
+                let avg_len = (self.total_lengths[i] as f64 / n).max(1.0);
+                let len_norm = 1.0 - BM25_B + BM25_B * indexed.lengths[i] as f64 / avg_len;
+                field.boost() * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * len_norm)
+            })
+            .sum()
+    }
+
+    fn build_hit(&self, indexed: &IndexedDocument, score: f64, terms: &HashSet<&str>) -> SearchHit {
+        let matched_fields: Vec<SearchField> = SearchField::ALL
+            .into_iter()
+            .filter(|field| {
+                terms
+                    .iter()
+                    .any(|t| indexed.term_freqs[field.index()].contains_key(*t))
+            })
+            .collect();
+
+        // Extrait tiré du champ le plus riche contenant une correspondance
+        let snippet_field = [
+            SearchField::Body,
+            SearchField::Description,
+            SearchField::References,
+            SearchField::Name,
+        ]
+        .into_iter()
+        .find(|f| matched_fields.contains(f))
+        .unwrap_or(SearchField::Description);
+
+        let document = &indexed.document;
+        SearchHit {
+            id: document.id.clone(),
+            kind: document.kind.clone(),
+            path: document.path.clone(),
+            source: document.source.clone(),
+            project_name: document.project_name.clone(),
+            name: document.name.clone(),
+            description: document.description.clone(),
+            score,
+            matched_fields,
+            snippet: build_snippet(document.field(snippet_field), terms),
+        }
+    }
+}
+
+/// Construit un extrait autour de la première correspondance avec les termes surlignés
+fn build_snippet(text: &str, terms: &HashSet<&str>) -> Vec<SnippetPart> {
+    let matches: Vec<(usize, usize)> = tokens_with_offsets(text)
+        .into_iter()
+        .filter(|(_, _, token)| terms.contains(token.as_str()))
+        .map(|(start, end, _)| (start, end))
+        .collect();
+
+    let first = matches.first().map(|m| m.0).unwrap_or(0);
+    let window_start = floor_char_boundary(text, first.saturating_sub(SNIPPET_RADIUS));
+    let window_end = floor_char_boundary(text, (first + SNIPPET_RADIUS).min(text.len()));
+
+    // Couper aux limites de mots pour un extrait lisible
+    let window_start = if window_start == 0 {
+        0
+    } else {
+        text[window_start..first]
+            .find(char::is_whitespace)
+            .map(|p| window_start + p + 1)
+            .unwrap_or(window_start)
+    };
+    let window_end = if window_end == text.len() {
+        window_end
+    } else {
+        text[..window_end]
+            .rfind(char::is_whitespace)
+            .filter(|p| *p > first)
+            .unwrap_or(window_end)
+    };
+
+    let mut parts = Vec::new();
+    let mut cursor = window_start;
+    if window_start > 0 {
+        parts.push(SnippetPart { text: "…".into(), highlight: false });
+    }
+    for (start, end) in matches {
+        if start < cursor || end > window_end {
+            continue;
+        }
+        if start > cursor {
+            parts.push(SnippetPart { text: text[cursor..start].to_string(), highlight: false });
+        }
+        parts.push(SnippetPart { text: text[start..end].to_string(), highlight: true });
+        cursor = end;
+    }
+    if cursor < window_end {
+        parts.push(SnippetPart { text: text[cursor..window_end].to_string(), highlight: false });
+    }
+    if window_end < text.len() {
+        parts.push(SnippetPart { text: "…".into(), highlight: false });
+    }
+
+    parts
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while index > 0 && !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, name: &str, description: &str, body: &str) -> SearchDocument {
+        SearchDocument {
+            id: id.into(),
+            kind: DocumentKind::Skill,
+            path: format!("/skills/{}", id),
+            source: SkillSource::Global,
+            project_name: None,
+            name: name.into(),
+            description: description.into(),
+            body: body.into(),
+            references: String::new(),
+        }
+    }
+
+    fn sample_index() -> SearchIndex {
+        let mut index = SearchIndex::default();
+        index.upsert(doc("1", "docker-deploy", "Deploys containers with docker compose", "Run docker compose up"));
+        index.upsert(doc("2", "pdf-report", "Generates PDF reports", "Uses a docker image to render"));
+        index.upsert(doc("3", "git-commit", "Creates conventional commits", "Stage files then commit"));
+        index
+    }
+
+    #[test]
+    fn test_name_matches_rank_first() {
+        let hits = sample_index().search("docker", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].id, "1");
+        assert!(hits[0].matched_fields.contains(&SearchField::Name));
+    }
+
+    #[test]
+    fn test_fuzzy_and_prefix_matching() {
+        let index = sample_index();
+        assert_eq!(index.search("dokcer", 10).first().map(|h| h.id.as_str()), Some("1"));
+        assert_eq!(index.search("conven", 10).first().map(|h| h.id.as_str()), Some("3"));
+    }
+
+    #[test]
+    fn test_remove_updates_postings() {
+        let mut index = sample_index();
+        assert!(index.remove("1"));
+        assert!(!index.remove("1"));
+        let hits = index.search("docker", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "2");
+    }
+
+    #[test]
+    fn test_snippet_highlights_matches() {
+        let hits = sample_index().search("render", 10);
+        let highlighted: Vec<&str> = hits[0]
+            .snippet
+            .iter()
+            .filter(|p| p.highlight)
+            .map(|p| p.text.as_str())
+            .collect();
+        assert_eq!(highlighted, vec!["render"]);
+    }
+}
@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Dossiers de structure autorisés à la racine d'un skill
+pub const STRUCTURE_FOLDERS: [&str; 3] = ["scripts", "references", "assets"];
+
+const FRONTMATTER: &str = "---
+name: {{name}}
+description: {{description}}
+license: MIT
+compatibility: claude-code
+metadata:
+  category: {{category}}
+  version: 1.0.0
+  author: {{author}}
+---
+";
+
+const MINIMAL_BODY: &str = "# {{title}}
+
+## Instructions
+
+Describe what Claude should do, step by step.
+
+1. Read the relevant context
+2. Apply the project conventions
+3. Report what was done
+
+## Examples
+
+### Example 1: typical request
+
+User says: \"{{title}}\"
+
+Actions:
+1. ...
+
+Result: ...
+
+## Troubleshooting
+
+### The skill is not loaded
+
+Check that the description mentions the trigger phrases used in the request.
+";
+
+const WORKFLOW_BODY: &str = "# {{title}}
+
+## Instructions
+
+### Step 1: gather context
+
+Collect the inputs required by the workflow.
+
+### Step 2: run the workflow
+
+Run `scripts/` helpers when available and check each result before moving on.
+
+### Step 3: report
+
+Summarize what was done and what remains.
+
+## Examples
+
+### Example 1: run the workflow
+
+User says: \"run {{title}}\"
+
+Actions:
+1. Gather context
+2. Run the workflow
+3. Report
+
+Result: the workflow completes and a summary is shown.
+
+## Troubleshooting
+
+### A step fails
+
+Stop, show the error output and suggest a fix before retrying.
+";
+
+const DOCUMENT_BODY: &str = "# {{title}}
+
+## Instructions
+
+### Structure
+
+Follow the outline described in `references/` and reuse files from `assets/`.
+
+### Style
+
+Keep sections short, use headings and tables where they help.
+
+## Examples
+
+### Example 1: new document
+
+User says: \"write a {{title}}\"
+
+Actions:
+1. Ask for missing inputs
+2. Draft the document following the structure
+3. Review formatting
+
+Result: a complete {{title}} document.
+
+## Troubleshooting
+
+### The output does not match the expected format
+
+Re-read the structure in `references/` and regenerate the affected sections.
+";
+
+const MCP_BODY: &str = "# {{title}}
+
+## Instructions
+
+### Available tools
+
+List the MCP server tools this skill relies on and when to call each one.
+
+### Workflow
+
+1. Check the MCP server is connected
+2. Call the tools in order
+3. Validate the returned data
+
+## Examples
+
+### Example 1: fetch data
+
+User says: \"use {{title}} tools\"
+
+Actions:
+1. Call the relevant MCP tool
+2. Summarize the result
+
+Result: the requested data is shown to the user.
+
+## Troubleshooting
+
+### MCP connection failed
+
+Check that the server is enabled in `.mcp.json` or `~/.claude.json` and restart Claude Code.
+";
+
+/// Modèle de skill (intégré ou défini par l'utilisateur)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillTemplate {
+    pub id: String,
+    pub label: String,
+    pub category: String,
+    pub builtin: bool,
+    pub folders: Vec<String>,
+    #[serde(skip)]
+    pub description: String,
+    #[serde(skip)]
+    pub content: String,
+    #[serde(skip)]
+    pub source_dir: Option<PathBuf>,
+}
+
+/// Modèles intégrés, alignés sur les catégories détectées par l'analyseur
+pub fn builtin_templates() -> Vec<SkillTemplate> {
+    let builtin = |id: &str, label: &str, category: &str, folders: &[&str], description: &str, body: &str| {
+        SkillTemplate {
+            id: id.into(),
+            label: label.into(),
+            category: category.into(),
+            builtin: true,
+            folders: folders.iter().map(|f| f.to_string()).collect(),
+            description: description.into(),
+            content: format!("{}\n{}", FRONTMATTER, body),
+            source_dir: None,
+        }
+    };
+
+    vec![
+        builtin(
+            "minimal",
+            "Minimal",
+            "",
+            &["references"],
+            "Implements {{title}} tasks following project conventions. Use when the user asks about {{title}}. Triggers on '{{title}}', '{{name}}'.",
+            MINIMAL_BODY,
+        ),
+        builtin(
+            "workflow",
+            "Workflow",
+            "workflow-automation",
+            &["scripts", "references"],
+            "Manages the {{title}} workflow step by step. Use when the user wants to run, automate or repeat {{title}}. Triggers on 'run {{title}}', 'automate {{title}}'.",
+            WORKFLOW_BODY,
+        ),
+        builtin(
+            "document-creation",
+            "Création de documents",
+            "document-creation",
+            &["references", "assets"],
+            "Generates {{title}} documents with a consistent structure. Use when the user asks to write, draft or export a {{title}}. Triggers on 'write {{title}}', 'draft {{title}}'.",
+            DOCUMENT_BODY,
+        ),
+        builtin(
+            "mcp-enhancement",
+            "Extension MCP",
+            "mcp-enhancement",
+            &["references"],
+            "Builds on MCP server tools to handle {{title}}. Use when the user needs data or actions from the {{title}} integration. Triggers on '{{title}}', 'use {{title}} tools'.",
+            MCP_BODY,
+        ),
+    ]
+}
+
+/// Charge les modèles utilisateur : chaque sous-dossier contenant SKILL.md est un modèle
+pub fn load_user_templates(templates_dir: &Path) -> Vec<SkillTemplate> {
+    let Ok(entries) = fs::read_dir(templates_dir) else {
+        return Vec::new();
+    };
+
+    let mut templates: Vec<SkillTemplate> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join("SKILL.md").is_file())
+        .filter_map(|dir| {
+            let content = fs::read_to_string(dir.join("SKILL.md")).ok()?;
+            let id = dir.file_name()?.to_str()?.to_string();
+            let folders = STRUCTURE_FOLDERS
+                .iter()
+                .filter(|f| dir.join(f).is_dir())
+                .map(|f| f.to_string())
+                .collect();
+            Some(SkillTemplate {
+                label: id.clone(),
+                id,
+                category: "custom".into(),
+                builtin: false,
+                folders,
+                description: String::new(),
+                content,
+                source_dir: Some(dir),
+            })
+        })
+        .collect();
+
+    templates.sort_by(|a, b| a.id.cmp(&b.id));
+    templates
+}
+
+/// Valeurs injectées dans les placeholders `{{...}}` d'un modèle
+pub struct TemplateVars {
+    pub name: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub author: String,
+}
+
+/// Génère le contenu de SKILL.md : les valeurs sont échappées en YAML dans le frontmatter
+pub fn render_template(template: &SkillTemplate, vars: &TemplateVars) -> String {
+    let title = vars.name.replace('-', " ");
+    let description = vars
+        .description
+        .clone()
+        .unwrap_or_else(|| template.description.replace("{{title}}", &title).replace("{{name}}", &vars.name));
+
+    let (frontmatter, body) = split_frontmatter(&template.content);
+    let values = [
+        ("name", vars.name.as_str()),
+        ("description", description.as_str()),
+        ("category", vars.category.as_str()),
+        ("author", vars.author.as_str()),
+        ("title", title.as_str()),
+    ];
+
+    let mut rendered_frontmatter = frontmatter.to_string();
+    let mut rendered_body = body.to_string();
+    for (key, value) in values {
+        let placeholder = format!("{{{{{}}}}}", key);
+        rendered_frontmatter = rendered_frontmatter.replace(&placeholder, &yaml_scalar(value));
+        rendered_body = rendered_body.replace(&placeholder, value);
+    }
+
+    format!("{}{}", rendered_frontmatter, rendered_body)
+}
+
+/// Crée le dossier du skill : SKILL.md, dossiers de structure et fichiers du modèle utilisateur.
+/// En cas d'échec, le dossier est supprimé s'il a été créé ici
+pub fn scaffold_skill(
+    skill_dir: &Path,
+    template: &SkillTemplate,
+    vars: &TemplateVars,
+    folders: &[String],
+) -> std::io::Result<()> {
+    let existed = skill_dir.exists();
+    fs::create_dir_all(skill_dir)?;
+    let result = fill_skill_dir(skill_dir, template, vars, folders);
+    if result.is_err() && !existed {
+        let _ = fs::remove_dir_all(skill_dir);
+    }
+    result
+}
+
+fn fill_skill_dir(
+    skill_dir: &Path,
+    template: &SkillTemplate,
+    vars: &TemplateVars,
+    folders: &[String],
+) -> std::io::Result<()> {
+
+    if let Some(source_dir) = &template.source_dir {
+        for entry in WalkDir::new(source_dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+            let relative = entry.path().strip_prefix(source_dir).unwrap_or(entry.path());
+            if relative == Path::new("SKILL.md") {
+                continue;
+            }
+            let target = skill_dir.join(relative);
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                fs::copy(entry.path(), &target)?;
+            }
+        }
+    }
+
+    for folder in folders {
+        let dir = skill_dir.join(folder);
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join(".gitkeep"), "")?;
+        }
+    }
+
+    fs::write(skill_dir.join("SKILL.md"), render_template(template, vars))
+}
+
+fn split_frontmatter(content: &str) -> (&str, &str) {
+    if !content.starts_with("---") {
+        return ("", content);
+    }
+    match content[3..].find("\n---") {
+        Some(pos) => {
+            let end = pos + 3 + 4;
+            let end = content[end..].find('\n').map(|nl| end + nl + 1).unwrap_or(content.len());
+            content.split_at(end)
+        }
+        None => ("", content),
+    }
+}
+
+/// Représentation YAML d'une chaîne sur une ligne (guillemets ajoutés si nécessaire)
+fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|s| s.trim_end().to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::analysis::analyze_skill;
+
+    #[test]
+    fn test_builtin_templates_score_100() {
+        let base = std::env::temp_dir().join(format!("skill-templates-{}", std::process::id()));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        for template in builtin_templates() {
+            let name = format!("sample-{}", template.id);
+            let dir = base.join(&name);
+            let vars = TemplateVars {
+                name: name.clone(),
+                description: None,
+                category: if template.category.is_empty() { "workflow-automation".into() } else { template.category.clone() },
+                author: "tester".into(),
+            };
+            scaffold_skill(&dir, &template, &vars, &template.folders).unwrap();
+
+            let analysis = runtime
+                .block_on(analyze_skill(dir.to_string_lossy().to_string()))
+                .unwrap();
+            let codes: Vec<&str> = analysis.issues.iter().map(|i| i.code.as_str()).collect();
+            assert_eq!(analysis.score, 100, "{}: {:?}", template.id, codes);
+        }
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_failure_removes_directory() {
+        let base = std::env::temp_dir().join(format!("skill-scaffold-fail-{}", std::process::id()));
+        let source = base.join("template");
+        fs::create_dir_all(&source).unwrap();
+        std::os::unix::fs::symlink(base.join("missing"), source.join("broken")).unwrap();
+
+        let template = SkillTemplate { source_dir: Some(source), ..builtin_templates()[0].clone() };
+        let vars = TemplateVars {
+            name: "broken-skill".into(),
+            description: None,
+            category: "workflow-automation".into(),
+            author: "tester".into(),
+        };
+        let dir = base.join("broken-skill");
+        assert!(scaffold_skill(&dir, &template, &vars, &[]).is_err());
+        assert!(!dir.exists());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_frontmatter_values_are_escaped() {
+        let template = &builtin_templates()[0];
+        let vars = TemplateVars {
+            name: "my-skill".into(),
+            description: Some("Creates things: fast. Use when 'x'".into()),
+            category: "workflow-automation".into(),
+            author: "me".into(),
+        };
+        let content = render_template(template, &vars);
+        let (frontmatter, _) = crate::utils::markdown_parser::parse_frontmatter(&content).unwrap();
+        assert_eq!(frontmatter.description.as_deref(), Some("Creates things: fast. Use when 'x'"));
+        assert!(content.contains("# my skill"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::models::config::Config;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Forme de la cible écrite dans un symlink
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkStyle {
+    #[default]
+    Absolute,
+    Relative,
+}
+
+/// Supprime `.` et `..` sans toucher au disque (la cible peut ne plus exister)
+pub fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            // `..` en tête d'un chemin relatif est conservé ; au-dessus de `/`, il est ignoré
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Cible visée par un symlink : `~` expansé, chemin relatif résolu depuis le dossier du lien
+pub fn intended_target(link: &Path) -> io::Result<PathBuf> {
+    let raw = fs::read_link(link)?;
+    let expanded = PathBuf::from(Config::expand_tilde(&raw.to_string_lossy()));
+    if expanded.is_absolute() {
+        return Ok(normalize_lexically(&expanded));
+    }
+
+    // Le dossier parent est canonisé pour suivre les parents eux-mêmes symlinkés
+    let parent = link.parent().unwrap_or(Path::new("/"));
+    let parent = parent.canonicalize().unwrap_or_else(|_| parent.to_path_buf());
+    Ok(normalize_lexically(&parent.join(expanded)))
+}
+
+/// Chemin de `target` relatif au dossier qui contiendra `link`
+pub fn relative_target(link: &Path, target: &Path) -> PathBuf {
+    let parent = link.parent().unwrap_or(Path::new("/"));
+    let from = parent.canonicalize().unwrap_or_else(|_| normalize_lexically(parent));
+    let to = target.canonicalize().unwrap_or_else(|_| normalize_lexically(target));
+
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component.as_os_str());
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Crée `link` pointant vers `target` dans le style demandé
+pub fn create_symlink(target: &Path, link: &Path, style: LinkStyle) -> io::Result<()> {
+    let written = match style {
+        LinkStyle::Absolute => target.canonicalize()?,
+        LinkStyle::Relative => relative_target(link, target),
+    };
+    std::os::unix::fs::symlink(written, link)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("symlinks-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn test_normalize_lexically() {
+        assert_eq!(normalize_lexically(Path::new("/a/b/../c/./d")), PathBuf::from("/a/c/d"));
+        assert_eq!(normalize_lexically(Path::new("../x")), PathBuf::from("../x"));
+        assert_eq!(normalize_lexically(Path::new("a/../../../x")), PathBuf::from("../../x"));
+        assert_eq!(normalize_lexically(Path::new("/../etc")), PathBuf::from("/etc"));
+    }
+
+    #[test]
+    fn test_relative_link_resolves_and_dangles() {
+        let root = temp_dir("relative");
+        let skill = root.join("global/skills/review");
+        let links = root.join("project/.claude/skills");
+        fs::create_dir_all(&skill).unwrap();
+        fs::create_dir_all(&links).unwrap();
+
+        let link = links.join("review");
+        create_symlink(&skill, &link, LinkStyle::Relative).unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("../../../global/skills/review"));
+        assert_eq!(intended_target(&link).unwrap(), skill);
+        assert_eq!(link.canonicalize().unwrap(), skill);
+
+        // Cible supprimée : le lien pend mais la cible visée reste connue
+        fs::remove_dir_all(&skill).unwrap();
+        assert!(link.canonicalize().is_err());
+        assert_eq!(intended_target(&link).unwrap(), skill);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+/// Mots vides ignorés lors de l'indexation et des comparaisons (anglais + français)
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "in", "is", "it",
+    "of", "on", "or", "that", "the", "this", "to", "use", "when", "with", "au", "aux", "ce",
+    "ces", "dans", "de", "des", "du", "en", "et", "la", "le", "les", "ou", "par", "pour",
+    "quand", "sur", "un", "une",
+];
+
+/// Normalise un mot : minuscules et suppression des accents courants
+pub fn normalize(word: &str) -> String {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'â' | 'ä' | 'á' | 'ã' => 'a',
+            'ç' => 'c',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' | 'í' | 'ì' => 'i',
+            'ô' | 'ö' | 'ó' | 'ò' | 'õ' => 'o',
+            'ù' | 'û' | 'ü' | 'ú' => 'u',
+            'ÿ' => 'y',
+            'ñ' => 'n',
+            other => other,
+        })
+        .collect()
+}
+
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word)
+}
+
+/// Découpe un texte en tokens normalisés avec leurs positions (octets) dans le texte d'origine
+///
+/// Les mots vides et les tokens d'un seul caractère sont ignorés.
+pub fn tokens_with_offsets(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            if start.is_none() {
+                start = Some(i);
+            }
+        } else if let Some(s) = start.take() {
+            push_token(&mut tokens, text, s, i);
+        }
+    }
+    if let Some(s) = start {
+        push_token(&mut tokens, text, s, text.len());
+    }
+
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<(usize, usize, String)>, text: &str, start: usize, end: usize) {
+    let word = normalize(&text[start..end]);
+    if word.chars().count() > 1 && !is_stopword(&word) {
+        tokens.push((start, end, word));
+    }
+}
+
+/// Découpe un texte en tokens normalisés (sans positions)
+pub fn tokenize(text: &str) -> Vec<String> {
+    tokens_with_offsets(text)
+        .into_iter()
+        .map(|(_, _, token)| token)
+        .collect()
+}
+
+/// Distance d'édition entre deux mots (Levenshtein + transposition de lettres adjacentes)
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_normalizes_and_filters() {
+        let tokens = tokenize("Génère un Rapport PDF pour l'équipe, x");
+        assert_eq!(tokens, vec!["genere", "rapport", "pdf", "equipe"]);
+    }
+
+    #[test]
+    fn test_tokens_keep_original_offsets() {
+        let text = "Créer un composant";
+        let tokens = tokens_with_offsets(text);
+        assert_eq!(&text[tokens[0].0..tokens[0].1], "Créer");
+        assert_eq!(tokens[1].2, "composant");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("skill", "skill"), 0);
+        assert_eq!(edit_distance("skil", "skill"), 1);
+        assert_eq!(edit_distance("dokcer", "docker"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
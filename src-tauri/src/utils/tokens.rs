@@ -0,0 +1,70 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+fn pretokenizer() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // Découpage inspiré des tokenizers BPE (cl100k) : contractions, mots, nombres, ponctuation, espaces
+        Regex::new(r"(?i)'(?:s|t|re|ve|m|ll|d)| ?\p{L}+| ?\p{N}{1,3}| ?[^\s\p{L}\p{N}]+|\s+")
+            .expect("pretokenizer regex")
+    })
+}
+
+/// Estimation hors-ligne du nombre de tokens d'un texte (approximation d'un BPE)
+///
+/// Les mots ASCII courts valent un token, les mots longs environ un token par 4 caractères,
+/// les mots non-ASCII environ un token par 3 octets.
+pub fn estimate_tokens(text: &str) -> usize {
+    pretokenizer()
+        .find_iter(text)
+        .map(|m| piece_tokens(m.as_str()))
+        .sum()
+}
+
+fn piece_tokens(piece: &str) -> usize {
+    let word = piece.strip_prefix(' ').unwrap_or(piece);
+    let first = match word.chars().next() {
+        Some(c) => c,
+        None => return 1,
+    };
+
+    if first.is_whitespace() {
+        // Les retours à la ligne consécutifs forment un seul token, les indentations aussi
+        return word.matches('\n').count().clamp(1, 2);
+    }
+    if first.is_alphabetic() {
+        if word.is_ascii() {
+            let len = word.len();
+            return if len <= 7 { 1 } else { len.div_ceil(4) };
+        }
+        return word.len().div_ceil(3).max(1);
+    }
+    if first.is_numeric() {
+        return 1;
+    }
+    // Ponctuation : les séquences courantes (`##`, `**`, `---`) fusionnent souvent
+    word.chars().count().div_ceil(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_english_sentence() {
+        // « Hello world, this is a test. » fait 8 tokens en cl100k
+        let estimate = estimate_tokens("Hello world, this is a test.");
+        assert!((7..=9).contains(&estimate), "estimate = {}", estimate);
+    }
+
+    #[test]
+    fn test_empty_text() {
+        assert_eq!(estimate_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_long_words_cost_more() {
+        assert!(estimate_tokens("internationalization") > estimate_tokens("skill"));
+        assert!(estimate_tokens("éléphant") >= 2);
+    }
+}
@@ -0,0 +1,315 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Longueur maximale du premier prompt affiché dans la liste des sessions
+const PROMPT_PREVIEW_CHARS: usize = 200;
+/// Contexte conservé de part et d'autre d'une occurrence trouvée
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+/// Résumé d'une session Claude Code (un fichier .jsonl)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub path: String,
+    pub cwd: Option<String>,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub duration_secs: Option<u64>,
+    pub message_count: usize,
+    pub model: Option<String>,
+    pub first_prompt: Option<String>,
+}
+
+/// Bloc de contenu d'un message ; les types de blocs inconnus sont ignorés
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageBlock {
+    Text { text: String },
+    Thinking { text: String },
+    ToolUse { id: String, name: String, input: Value },
+    ToolResult { tool_use_id: String, content: String, is_error: bool },
+}
+
+/// Message utilisateur ou assistant d'une transcription
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptMessage {
+    pub index: usize,
+    pub uuid: Option<String>,
+    pub role: String,              // "user" | "assistant"
+    pub timestamp: Option<String>,
+    pub model: Option<String>,
+    pub is_meta: bool,             // Message injecté par Claude Code (contexte, rappel...)
+    pub blocks: Vec<MessageBlock>,
+}
+
+impl TranscriptMessage {
+    /// Texte du prompt saisi : texte brut, ou nom et arguments d'une commande slash
+    pub fn prompt_text(&self) -> Option<String> {
+        if self.role != "user" || self.is_meta {
+            return None;
+        }
+        let text = self.first_text()?;
+        if let Some(name) = self.slash_command() {
+            let args = tag_content(text, "command-args").unwrap_or_default();
+            return Some(format!("{} {}", name, args).trim().to_string());
+        }
+        // Sorties de commandes locales, rappels système...
+        if text.trim_start().starts_with('<') {
+            return None;
+        }
+        Some(text.trim().to_string())
+    }
+
+    /// Commande slash saisie par l'utilisateur (ex. "/review"), d'après le balisage de Claude Code
+    pub fn slash_command(&self) -> Option<&str> {
+        if self.role != "user" || self.is_meta {
+            return None;
+        }
+        tag_content(self.first_text()?, "command-name")
+    }
+
+    fn first_text(&self) -> Option<&str> {
+        self.blocks.iter().find_map(|b| match b {
+            MessageBlock::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Texte cherchable : texte, raisonnement, entrées et résultats d'outils
+    pub fn searchable_text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|b| match b {
+                MessageBlock::Text { text } | MessageBlock::Thinking { text } => text.clone(),
+                MessageBlock::ToolUse { name, input, .. } => format!("{} {}", name, input),
+                MessageBlock::ToolResult { content, .. } => content.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn tag_content<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+    let start = text.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = text[start..].find(&format!("</{}>", tag))? + start;
+    Some(text[start..end].trim())
+}
+
+/// Convertit une ligne de transcription en message ; None pour les autres types d'enregistrements
+/// (résumés, instantanés de fichiers...) et les lignes illisibles
+pub fn parse_record(line: &str, index: usize) -> Option<TranscriptMessage> {
+    let record: Value = serde_json::from_str(line).ok()?;
+    let role = record.get("type").and_then(Value::as_str)?;
+    if role != "user" && role != "assistant" {
+        return None;
+    }
+    let message = record.get("message")?;
+    let blocks = match message.get("content")? {
+        Value::String(text) => vec![MessageBlock::Text { text: text.clone() }],
+        Value::Array(blocks) => blocks.iter().filter_map(parse_block).collect(),
+        _ => Vec::new(),
+    };
+    let text_field = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+
+    Some(TranscriptMessage {
+        index,
+        uuid: text_field(&record, "uuid"),
+        role: role.to_string(),
+        timestamp: text_field(&record, "timestamp"),
+        model: text_field(message, "model"),
+        is_meta: record.get("isMeta").and_then(Value::as_bool).unwrap_or(false),
+        blocks,
+    })
+}
+
+fn parse_block(block: &Value) -> Option<MessageBlock> {
+    let text = |key: &str| block.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+    match block.get("type").and_then(Value::as_str)? {
+        "text" => Some(MessageBlock::Text { text: text("text") }),
+        "thinking" => Some(MessageBlock::Thinking { text: text("thinking") }),
+        "tool_use" => Some(MessageBlock::ToolUse {
+            id: text("id"),
+            name: text("name"),
+            input: block.get("input").cloned().unwrap_or(Value::Null),
+        }),
+        "tool_result" => {
+            // Contenu texte ou liste de blocs (texte, images...)
+            let content = match block.get("content") {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Array(parts)) => parts
+                    .iter()
+                    .filter_map(|p| p.get("text").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+            Some(MessageBlock::ToolResult {
+                tool_use_id: text("tool_use_id"),
+                content,
+                is_error: block.get("is_error").and_then(Value::as_bool).unwrap_or(false),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Lit les messages d'une transcription ligne par ligne ; `visit` renvoie false pour arrêter
+pub fn read_messages(path: &Path, mut visit: impl FnMut(TranscriptMessage) -> bool) -> std::io::Result<()> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut index = 0;
+    for line in reader.lines().map_while(Result::ok) {
+        if let Some(message) = parse_record(&line, index) {
+            index += 1;
+            if !visit(message) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Résumé d'une session en une lecture du fichier
+pub fn summarize_session(path: &Path) -> std::io::Result<SessionSummary> {
+    let mut summary = SessionSummary {
+        id: path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+        path: path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut index = 0;
+    for line in reader.lines().map_while(Result::ok) {
+        if summary.cwd.is_none() {
+            summary.cwd = serde_json::from_str::<Value>(&line)
+                .ok()
+                .and_then(|r| r.get("cwd").and_then(Value::as_str).map(String::from));
+        }
+        let Some(message) = parse_record(&line, index) else {
+            continue;
+        };
+        index += 1;
+        summary.message_count += 1;
+        if let Some(timestamp) = message.timestamp.clone() {
+            summary.started_at.get_or_insert_with(|| timestamp.clone());
+            summary.ended_at = Some(timestamp);
+        }
+        if message.model.is_some() {
+            summary.model = message.model.clone();
+        }
+        if summary.first_prompt.is_none() {
+            summary.first_prompt = message.prompt_text().map(|p| p.chars().take(PROMPT_PREVIEW_CHARS).collect());
+        }
+    }
+
+    summary.duration_secs = match (&summary.started_at, &summary.ended_at) {
+        (Some(start), Some(end)) => match (parse_timestamp(start), parse_timestamp(end)) {
+            (Some(start), Some(end)) if end >= start => Some(((end - start) / 1000) as u64),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(summary)
+}
+
+/// Horodatage RFC 3339 en UTC ("2025-06-01T12:34:56.789Z") en millisecondes depuis l'époque Unix
+pub fn parse_timestamp(timestamp: &str) -> Option<i64> {
+    let (date, time) = timestamp.trim_end_matches('Z').split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+    let (clock, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let mut clock_parts = clock.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hour, minute, second) = (clock_parts.next()??, clock_parts.next()??, clock_parts.next()??);
+    let millis: i64 = format!("{:0<3}", fraction).get(..3)?.parse().ok()?;
+
+    // Nombre de jours depuis 1970-01-01 (calendrier grégorien proleptique)
+    let (y, m) = if month <= 2 { (year - 1, month + 9) } else { (year, month - 3) };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000 + millis)
+}
+
+/// Extrait centré sur la première occurrence de `needle` (déjà en minuscules)
+pub fn snippet(text: &str, needle: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    let position = lower.find(needle)?;
+    // Les positions sont calculées en caractères : la mise en minuscules peut changer les longueurs en octets
+    let start_char = lower[..position].chars().count();
+    let needle_chars = needle.chars().count();
+    let chars: Vec<char> = text.chars().collect();
+    let from = start_char.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (start_char + needle_chars + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    let mut out = String::new();
+    if from > 0 {
+        out.push('…');
+    }
+    out.extend(chars[from.min(chars.len())..to].iter().map(|c| if c.is_whitespace() { ' ' } else { *c }));
+    if to < chars.len() {
+        out.push('…');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"summary","summary":"Fix login"}
+{"type":"user","isMeta":true,"timestamp":"2025-06-01T10:00:00.000Z","cwd":"/work/api","message":{"role":"user","content":"<local-command-caveat>ignore</local-command-caveat>"}}
+{"type":"user","timestamp":"2025-06-01T10:00:01.500Z","message":{"role":"user","content":"<command-message>review</command-message>\n<command-name>/review</command-name>\n<command-args>src/auth.rs</command-args>"}}
+{"type":"assistant","timestamp":"2025-06-01T10:01:00.000Z","message":{"model":"claude-sonnet-4","content":[{"type":"thinking","thinking":"Look at auth"},{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"src/auth.rs"}},{"type":"server_tool_use","id":"x"}]}}
+{"type":"user","timestamp":"2025-06-01T10:02:05.000Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":[{"type":"text","text":"fn login() { check_Password() }"}]}]}}
+{"type":"file-history-snapshot","snapshot":{}}
+not json
+"#;
+
+    #[test]
+    fn test_summarize_session() {
+        let path = std::env::temp_dir().join(format!("session-{}.jsonl", std::process::id()));
+        fs::write(&path, TRANSCRIPT).unwrap();
+
+        let summary = summarize_session(&path).unwrap();
+        assert_eq!(summary.message_count, 4);
+        assert_eq!(summary.cwd.as_deref(), Some("/work/api"));
+        assert_eq!(summary.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(summary.first_prompt.as_deref(), Some("/review src/auth.rs"));
+        assert_eq!(summary.duration_secs, Some(125));
+
+        let mut messages = Vec::new();
+        read_messages(&path, |m| {
+            messages.push(m);
+            true
+        })
+        .unwrap();
+        assert_eq!(messages[2].blocks.len(), 2);
+        assert!(matches!(&messages[2].blocks[1], MessageBlock::ToolUse { name, .. } if name == "Read"));
+        assert_eq!(
+            messages[3].blocks[0],
+            MessageBlock::ToolResult {
+                tool_use_id: "t1".into(),
+                content: "fn login() { check_Password() }".into(),
+                is_error: false
+            }
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_timestamp_and_snippet() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_timestamp("2024-02-29T12:00:00.25Z"), Some(1_709_208_000_250));
+        assert_eq!(parse_timestamp("yesterday"), None);
+
+        let text = format!("{} Ünïcode needle here", "x".repeat(100));
+        let found = snippet(&text, "needle").unwrap();
+        assert!(found.starts_with('…') && found.ends_with("needle here"));
+        assert_eq!(snippet("nothing", "needle"), None);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::utils::text::{normalize, tokenize};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Seuil de Jaccard (sur les tokens) à partir duquel deux trigger phrases sont équivalentes
+const PHRASE_MATCH_THRESHOLD: f64 = 0.8;
+
+fn trigger_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"'([^'\n]{2,80}?)'|"([^"\n]{2,80}?)"|“([^”\n]{2,80}?)”|«\s*([^»\n]{2,80}?)\s*»"#)
+            .expect("trigger regex")
+    })
+}
+
+/// Guillemets simples uniquement hors des mots, pour ignorer les apostrophes (don't, l'outil).
+/// Les séparateurs sont vérifiés sans être consommés : `'a' 'b'` donne bien deux phrases.
+fn outside_word(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    before.is_none_or(|c| c.is_whitespace() || "([,:;".contains(c))
+        && after.is_none_or(|c| c.is_whitespace() || ")],.;:!?".contains(c))
+}
+
+/// Extrait les trigger phrases (chaînes entre guillemets) d'une description
+pub fn extract_trigger_phrases(description: &str) -> Vec<String> {
+    let mut phrases: Vec<String> = Vec::new();
+    let mut at = 0;
+    while let Some(caps) = trigger_regex().captures_at(description, at) {
+        let whole = caps.get(0).expect("match");
+        // Apostrophe prise pour un guillemet : la recherche reprend juste après elle
+        if caps.get(1).is_some() && !outside_word(description, whole.start(), whole.end()) {
+            at = whole.start() + 1;
+            continue;
+        }
+        at = whole.end();
+        if let Some(m) = (1..=4).find_map(|i| caps.get(i)) {
+            let phrase = normalize(m.as_str().trim());
+            if !phrase.is_empty() && !phrases.contains(&phrase) {
+                phrases.push(phrase);
+            }
+        }
+    }
+    phrases
+}
+
+fn use_when_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b(?:use when|when to use|quand)\b").expect("use when regex"))
+}
+
+/// Extrait la clause « Use when ... » d'une description (jusqu'à la fin de la phrase).
+/// Les marqueurs ne sont reconnus que comme mots entiers (pas dans « misuse when » ou « quandary »)
+pub fn extract_use_when(description: &str) -> Option<String> {
+    let lower = description.to_lowercase();
+    let start = use_when_regex().find(&lower)?.end();
+    let rest = &lower[start..];
+    let end = rest.find(['.', '\n']).unwrap_or(rest.len());
+    let clause = rest[..end].trim_start_matches([':', ' ']).trim();
+    (!clause.is_empty()).then(|| clause.to_string())
+}
+
+/// Indice de Jaccard entre deux ensembles de tokens
+pub fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count() as f64;
+    let union = a.union(b).count() as f64;
+    intersection / union
+}
+
+/// Similarité cosinus entre deux textes (fréquences de tokens)
+pub fn cosine_similarity(a: &str, b: &str) -> f64 {
+    let freqs = |text: &str| {
+        let mut map: HashMap<String, f64> = HashMap::new();
+        for token in tokenize(text) {
+            *map.entry(token).or_insert(0.0) += 1.0;
+        }
+        map
+    };
+    let (fa, fb) = (freqs(a), freqs(b));
+    let dot: f64 = fa.iter().filter_map(|(t, x)| fb.get(t).map(|y| x * y)).sum();
+    let norm_a = fa.values().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = fb.values().map(|x| x * x).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Vrai si deux trigger phrases désignent la même intention (identiques ou quasi)
+pub fn phrases_match(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let ta: HashSet<String> = tokenize(a).into_iter().collect();
+    let tb: HashSet<String> = tokenize(b).into_iter().collect();
+    jaccard(&ta, &tb) >= PHRASE_MATCH_THRESHOLD
+}
+
+/// Trigger phrases de `a` qui ont un équivalent dans `b`
+pub fn shared_phrases(a: &[String], b: &[String]) -> Vec<String> {
+    a.iter()
+        .filter(|pa| b.iter().any(|pb| phrases_match(pa, pb)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_ignores_apostrophes() {
+        let desc = "Creates PDF reports. Use when the user doesn't want Word. Triggers on 'pdf report', \"export PDF\", “rapport”.";
+        assert_eq!(
+            extract_trigger_phrases(desc),
+            vec!["pdf report", "export pdf", "rapport"]
+        );
+    }
+
+    #[test]
+    fn test_extract_adjacent_single_quotes() {
+        assert_eq!(extract_trigger_phrases("Triggers: 'deploy app' 'ship it'"), vec!["deploy app", "ship it"]);
+        assert_eq!(
+            extract_trigger_phrases("Don't use for 'lint', 'format'. It's 'fast'"),
+            vec!["lint", "format", "fast"]
+        );
+    }
+
+    #[test]
+    fn test_extract_french_quotes() {
+        let desc = "Génère l'API. Déclencheurs : « nouvelle API », 'créer endpoint'";
+        assert_eq!(
+            extract_trigger_phrases(desc),
+            vec!["nouvelle api", "creer endpoint"]
+        );
+    }
+
+    #[test]
+    fn test_extract_use_when() {
+        let desc = "Generates reports. Use when the user asks for a weekly summary. Triggers on 'report'.";
+        assert_eq!(
+            extract_use_when(desc).as_deref(),
+            Some("the user asks for a weekly summary")
+        );
+        assert_eq!(extract_use_when("Generates reports."), None);
+    }
+
+    #[test]
+    fn test_extract_use_when_word_boundaries() {
+        assert_eq!(extract_use_when("Avoids misuse when parsing. Solves a quandary."), None);
+        assert_eq!(
+            extract_use_when("Avoids misuse when parsing. Quand: l'utilisateur demande un export.").as_deref(),
+            Some("l'utilisateur demande un export")
+        );
+    }
+
+    #[test]
+    fn test_shared_phrases() {
+        let a = vec!["create component".to_string(), "new page".to_string()];
+        let b = vec!["create a component".to_string(), "deploy".to_string()];
+        assert_eq!(shared_phrases(&a, &b), vec!["create component"]);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!(cosine_similarity("deploy docker containers", "deploy docker containers") > 0.99);
+        assert_eq!(cosine_similarity("deploy docker", "write poems"), 0.0);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::utils::claude_state::session_cwd;
+use crate::utils::transcripts::{parse_timestamp, read_messages, MessageBlock, TranscriptMessage};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Préfixe des outils fournis par les serveurs MCP (`mcp__<serveur>__<outil>`)
+const MCP_TOOL_PREFIX: &str = "mcp__";
+
+/// Version du format du cache : un cache d'une autre version est recalculé
+const USAGE_CACHE_VERSION: u32 = 1;
+
+/// Nombre d'utilisations et date de la dernière (timestamp en secondes)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageCounter {
+    pub count: usize,
+    pub last_used: Option<u64>,
+}
+
+impl UsageCounter {
+    fn record(&mut self, at: Option<u64>) {
+        self.count += 1;
+        self.last_used = self.last_used.max(at);
+    }
+
+    pub fn merge(&mut self, other: &UsageCounter) {
+        self.count += other.count;
+        self.last_used = self.last_used.max(other.last_used);
+    }
+}
+
+/// Totaux d'une journée (UTC)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub skill_loads: usize,
+    pub command_invocations: usize,
+    pub tool_uses: usize,
+    pub mcp_calls: usize,
+}
+
+impl DailyUsage {
+    fn add(&mut self, other: &DailyUsage) {
+        self.skill_loads += other.skill_loads;
+        self.command_invocations += other.command_invocations;
+        self.tool_uses += other.tool_uses;
+        self.mcp_calls += other.mcp_calls;
+    }
+}
+
+/// Utilisation des skills, commandes slash et outils sur un ensemble de sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub sessions: usize,
+    pub skills: BTreeMap<String, UsageCounter>,      // Nom du skill chargé par l'outil Skill
+    #[serde(default)]
+    pub skill_reads: BTreeMap<String, UsageCounter>, // Dossier d'un SKILL.md lu directement
+    pub commands: BTreeMap<String, UsageCounter>,    // Invocation, ex. "/git:commit"
+    pub tools: BTreeMap<String, UsageCounter>,
+    pub mcp_tools: BTreeMap<String, UsageCounter>,
+    pub daily: BTreeMap<String, DailyUsage>,         // Clé "AAAA-MM-JJ"
+}
+
+impl UsageStats {
+    /// Comptabilise les commandes slash saisies et les appels d'outils d'un message
+    pub fn record(&mut self, message: &TranscriptMessage) {
+        let at = message.timestamp.as_deref().and_then(parse_timestamp).map(|ms| (ms / 1000) as u64);
+        let day = message.timestamp.as_deref().and_then(|t| t.get(..10)).map(String::from);
+        let mut daily = DailyUsage::default();
+
+        if let Some(command) = message.slash_command() {
+            self.commands.entry(command.to_string()).or_default().record(at);
+            daily.command_invocations += 1;
+        }
+
+        for block in &message.blocks {
+            let MessageBlock::ToolUse { name, input, .. } = block else {
+                continue;
+            };
+            self.tools.entry(name.clone()).or_default().record(at);
+            daily.tool_uses += 1;
+            if name.starts_with(MCP_TOOL_PREFIX) {
+                self.mcp_tools.entry(name.clone()).or_default().record(at);
+                daily.mcp_calls += 1;
+            }
+            if let Some(load) = loaded_skill(name, input) {
+                let (counters, key) = match load {
+                    SkillLoad::Name(name) => (&mut self.skills, name),
+                    SkillLoad::Read(dir) => (&mut self.skill_reads, dir),
+                };
+                counters.entry(key).or_default().record(at);
+                daily.skill_loads += 1;
+            }
+            // Commande slash lancée par le modèle
+            if let Some(command) = invoked_command(name, input) {
+                self.commands.entry(command).or_default().record(at);
+                daily.command_invocations += 1;
+            }
+        }
+
+        if let Some(day) = day.filter(|_| daily != DailyUsage::default()) {
+            self.daily.entry(day).or_default().add(&daily);
+        }
+    }
+
+    pub fn merge(&mut self, other: &UsageStats) {
+        self.sessions += other.sessions;
+        for (mine, theirs) in [
+            (&mut self.skills, &other.skills),
+            (&mut self.skill_reads, &other.skill_reads),
+            (&mut self.commands, &other.commands),
+            (&mut self.tools, &other.tools),
+            (&mut self.mcp_tools, &other.mcp_tools),
+        ] {
+            for (name, counter) in theirs {
+                mine.entry(name.clone()).or_default().merge(counter);
+            }
+        }
+        for (day, usage) in &other.daily {
+            self.daily.entry(day.clone()).or_default().add(usage);
+        }
+    }
+}
+
+/// Chargement d'un skill : par son nom (outil Skill) ou par le dossier dont SKILL.md est lu
+enum SkillLoad {
+    Name(String),
+    Read(String),
+}
+
+/// Skill chargé par l'outil Skill, ou par lecture directe de son SKILL.md
+///
+/// Une lecture est gardée avec son dossier complet : deux skills de même nom ne se confondent pas.
+fn loaded_skill(tool: &str, input: &Value) -> Option<SkillLoad> {
+    match tool {
+        "Skill" => input
+            .get("skill")
+            .or_else(|| input.get("command"))
+            .and_then(Value::as_str)
+            .map(|s| SkillLoad::Name(s.trim_start_matches('/').to_string())),
+        "Read" => {
+            let path = Path::new(input.get("file_path").and_then(Value::as_str)?);
+            if path.file_name()? != "SKILL.md" {
+                return None;
+            }
+            path.parent()?.to_str().map(|dir| SkillLoad::Read(dir.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn invoked_command(tool: &str, input: &Value) -> Option<String> {
+    if tool != "SlashCommand" {
+        return None;
+    }
+    let command = input.get("command").and_then(Value::as_str)?.split_whitespace().next()?;
+    Some(format!("/{}", command.trim_start_matches('/')))
+}
+
+/// Utilisation calculée pour une transcription
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionUsage {
+    pub modified: u64,
+    pub size: u64,
+    pub cwd: Option<String>,
+    pub stats: UsageStats,
+}
+
+pub fn session_usage(transcript: &Path) -> Result<SessionUsage> {
+    let metadata = fs::metadata(transcript)?;
+    let mut stats = UsageStats { sessions: 1, ..Default::default() };
+    read_messages(transcript, |message| {
+        stats.record(&message);
+        true
+    })?;
+    Ok(SessionUsage {
+        modified: file_mtime(&metadata),
+        size: metadata.len(),
+        cwd: session_cwd(transcript).map(|p| p.to_string_lossy().to_string()),
+        stats,
+    })
+}
+
+fn file_mtime(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Cache persistant des comptages, par transcription : seules les sessions modifiées sont relues
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageCache {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub sessions: BTreeMap<String, SessionUsage>,
+}
+
+impl Default for UsageCache {
+    fn default() -> Self {
+        UsageCache { version: USAGE_CACHE_VERSION, sessions: BTreeMap::new() }
+    }
+}
+
+impl UsageCache {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<UsageCache>(&c).ok())
+            .filter(|cache| cache.version == USAGE_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Écriture dans un fichier temporaire puis renommage : un lecteur ne voit jamais un cache tronqué
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("usage-cache.json");
+        let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+        let written = fs::write(&temp, serde_json::to_string(self)?).and_then(|_| fs::rename(&temp, path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        Ok(written?)
+    }
+
+    /// Met le cache en phase avec les transcriptions existantes ; renvoie le nombre de sessions
+    /// relues ou retirées (0 : rien à enregistrer)
+    pub fn refresh(&mut self, transcripts: &[PathBuf]) -> usize {
+        let existing: std::collections::HashSet<String> =
+            transcripts.iter().map(|t| t.to_string_lossy().to_string()).collect();
+        let before = self.sessions.len();
+        self.sessions.retain(|path, _| existing.contains(path));
+
+        let mut changed = before - self.sessions.len();
+        for transcript in transcripts {
+            let key = transcript.to_string_lossy().to_string();
+            let Ok(metadata) = fs::metadata(transcript) else {
+                continue;
+            };
+            let fresh = self
+                .sessions
+                .get(&key)
+                .is_some_and(|s| s.modified == file_mtime(&metadata) && s.size == metadata.len());
+            if fresh {
+                continue;
+            }
+            if let Ok(usage) = session_usage(transcript) {
+                self.sessions.insert(key, usage);
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Statistiques regroupées par dossier de travail des sessions
+    pub fn by_project(&self) -> BTreeMap<String, UsageStats> {
+        let mut projects: BTreeMap<String, UsageStats> = BTreeMap::new();
+        for usage in self.sessions.values() {
+            let Some(cwd) = &usage.cwd else {
+                continue;
+            };
+            projects.entry(cwd.clone()).or_default().merge(&usage.stats);
+        }
+        projects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"user","cwd":"/work/api","timestamp":"2025-06-01T10:00:00.000Z","message":{"role":"user","content":"<command-name>/git:commit</command-name>"}}
+{"type":"assistant","timestamp":"2025-06-01T10:01:00.000Z","message":{"content":[{"type":"tool_use","id":"a","name":"Skill","input":{"skill":"pdf"}},{"type":"tool_use","id":"b","name":"Read","input":{"file_path":"/home/me/.claude/skills/review/SKILL.md"}}]}}
+{"type":"assistant","timestamp":"2025-06-02T09:00:00.000Z","message":{"content":[{"type":"tool_use","id":"c","name":"mcp__db__query","input":{}},{"type":"tool_use","id":"d","name":"SlashCommand","input":{"command":"/deploy staging"}}]}}
+{"type":"progress","data":{}}
+"#;
+
+    #[test]
+    fn test_session_usage_counts() {
+        let dir = std::env::temp_dir().join(format!("usage-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let transcript = dir.join("s1.jsonl");
+        fs::write(&transcript, TRANSCRIPT).unwrap();
+
+        let usage = session_usage(&transcript).unwrap();
+        let stats = &usage.stats;
+        assert_eq!(usage.cwd.as_deref(), Some("/work/api"));
+        assert_eq!(stats.skills.keys().collect::<Vec<_>>(), vec!["pdf"]);
+        assert_eq!(stats.skill_reads.keys().collect::<Vec<_>>(), vec!["/home/me/.claude/skills/review"]);
+        assert_eq!(stats.commands.keys().collect::<Vec<_>>(), vec!["/deploy", "/git:commit"]);
+        assert_eq!(stats.mcp_tools["mcp__db__query"].count, 1);
+        assert_eq!(stats.tools.values().map(|c| c.count).sum::<usize>(), 4);
+        assert_eq!(stats.skills["pdf"].last_used, Some(1_748_772_060));
+        assert_eq!(
+            stats.daily["2025-06-01"],
+            DailyUsage { skill_loads: 2, command_invocations: 1, tool_uses: 2, mcp_calls: 0 }
+        );
+        assert_eq!(stats.daily["2025-06-02"].mcp_calls, 1);
+
+        let mut cache = UsageCache::default();
+        assert_eq!(cache.refresh(std::slice::from_ref(&transcript)), 1);
+        assert_eq!(cache.refresh(std::slice::from_ref(&transcript)), 0);
+        let projects = cache.by_project();
+        assert_eq!(projects["/work/api"].sessions, 1);
+        assert_eq!(cache.refresh(&[]), 1);
+        assert!(cache.sessions.is_empty());
+
+        // Un cache d'un ancien format est ignoré
+        let cache_path = dir.join("cache.json");
+        fs::write(&cache_path, r#"{"sessions":{"s0":{"modified":1,"size":1,"cwd":null,"stats":{"sessions":1}}}}"#)
+            .unwrap();
+        assert!(UsageCache::load(&cache_path).sessions.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Variables stockées : nom → valeur
+pub type SecretMap = BTreeMap<String, String>;
+
+/// Fichier coffre : valeurs chiffrées (ChaCha20-Poly1305, clé dérivée Argon2id de la passphrase)
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Dérivation de clé impossible: {}", e))?;
+    Ok(Key::from(key))
+}
+
+/// Lit et déchiffre le coffre (vide s'il n'existe pas encore)
+pub fn read_vault(path: &Path, passphrase: &str) -> Result<SecretMap> {
+    if !path.exists() {
+        return Ok(SecretMap::new());
+    }
+
+    let file: VaultFile = serde_json::from_str(&fs::read_to_string(path)?).context("Coffre illisible")?;
+    let salt = BASE64.decode(file.salt)?;
+    let nonce = BASE64.decode(file.nonce)?;
+    let ciphertext = BASE64.decode(file.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(anyhow!("Coffre corrompu"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| anyhow!("Passphrase incorrecte ou coffre corrompu"))?;
+
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Chiffre et écrit le coffre (nouveau sel et nonce à chaque écriture, permissions 0600)
+pub fn write_vault(path: &Path, passphrase: &str, secrets: &SecretMap) -> Result<()> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let ciphertext = cipher
+        .encrypt(&nonce, serde_json::to_vec(secrets)?.as_ref())
+        .map_err(|_| anyhow!("Chiffrement impossible"))?;
+
+    let file = VaultFile {
+        version: 1,
+        kdf: "argon2id".into(),
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    write_private(path, &serde_json::to_string_pretty(&file)?)
+}
+
+/// Lit un fichier `.env` (`NOM=valeur`, guillemets optionnels, commentaires `#`)
+pub fn read_env_file(path: &Path) -> Result<SecretMap> {
+    if !path.exists() {
+        return Ok(SecretMap::new());
+    }
+
+    let mut secrets = SecretMap::new();
+    for line in fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim();
+            let value = if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+                value[1..value.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\")
+            } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+                value[1..value.len() - 1].to_string()
+            } else {
+                value.to_string()
+            };
+            secrets.insert(key.trim().to_string(), value);
+        }
+    }
+    Ok(secrets)
+}
+
+/// Écrit un fichier `.env` (valeurs entre guillemets, permissions 0600)
+pub fn write_env_file(path: &Path, secrets: &SecretMap) -> Result<()> {
+    let mut content = String::from("# Généré par Claude Skills Manager — ne pas committer\n");
+    for (key, value) in secrets {
+        content.push_str(&format!(
+            "{}=\"{}\"\n",
+            key,
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+    write_private(path, &content)
+}
+
+/// Écrit dans un fichier temporaire créé en 0600 puis renommé : le contenu n'est jamais lisible
+/// par d'autres, même brièvement, et un fichier existant ne garde pas ses anciennes permissions
+fn write_private(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("secrets");
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let _ = fs::remove_file(&temp);
+
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&temp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    Ok(written?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_roundtrip() {
+        let path = std::env::temp_dir().join(format!("vault-test-{}.json", std::process::id()));
+        let mut secrets = SecretMap::new();
+        secrets.insert("GITHUB_TOKEN".into(), "ghp_secret".into());
+
+        write_vault(&path, "correct horse", &secrets).unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("ghp_secret"));
+
+        assert_eq!(read_vault(&path, "correct horse").unwrap(), secrets);
+        assert!(read_vault(&path, "wrong").is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_env_file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("env-test-{}.env", std::process::id()));
+        let mut secrets = SecretMap::new();
+        secrets.insert("API_KEY".into(), "a\"b\\c d".into());
+        secrets.insert("TOKEN".into(), "plain".into());
+
+        // Un fichier existant lisible par tous est remplacé par un fichier 0600
+        use std::os::unix::fs::PermissionsExt;
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_env_file(&path, &secrets).unwrap();
+        assert_eq!(read_env_file(&path).unwrap(), secrets);
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        fs::remove_file(&path).unwrap();
+    }
+}
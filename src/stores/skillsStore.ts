@@ -1,14 +1,15 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
-import type { Skill, Project, Config, SkillAnalysis } from '../types/skill'
+import type { Skill, Command, Project, Config, SkillAnalysis, LinkStyle } from '../types/skill'
+import type { UsageCounter } from '../types/transcript'
 import { invoke } from '@tauri-apps/api/core'
 
 export const useSkillsStore = defineStore('skills', () => {
   const skills = ref<Skill[]>([])
-  const commands = ref<Skill[]>([])
+  const commands = ref<Command[]>([])
   const projects = ref<Project[]>([])
   const currentSkill = ref<Skill | null>(null)
-  const currentCommand = ref<Skill | null>(null)
+  const currentCommand = ref<Command | null>(null)
   const analyses = ref<Record<string, SkillAnalysis>>({})
   const analyzing = ref(false)
   const loading = ref(false)
@@ -100,11 +101,12 @@ export const useSkillsStore = defineStore('skills', () => {
         console.log('📂 Project paths for skills:', projectPaths)
       }
       skills.value = await invoke<Skill[]>('list_skills', { projectPaths })
-      commands.value = await invoke<Skill[]>('list_commands', { projectPaths })
+      commands.value = await invoke<Command[]>('list_commands', { projectPaths })
       if (import.meta.env.DEV) {
         console.log('📚 Skills loaded:', skills.value.length, 'skills,', commands.value.length, 'commands')
       }
-      // Analyser tous les skills en arrière-plan (sans bloquer le chargement)
+      // Utilisation et analyse en arrière-plan (sans bloquer le chargement)
+      loadUsage(projectPaths)
       analyzeAllSkills()
     } catch (e) {
       const errorMessage = e instanceof Error ? e.message : 'Une erreur inconnue est survenue'
@@ -160,7 +162,7 @@ export const useSkillsStore = defineStore('skills', () => {
     currentCommand.value = null
   }
 
-  async function createSymlink(skillId: string, targetProjectPath: string) {
+  async function createSymlink(skillId: string, targetProjectPath: string, linkStyle: LinkStyle = 'absolute') {
     try {
       const skill = skills.value.find(s => s.id === skillId)
       if (!skill) {
@@ -169,7 +171,8 @@ export const useSkillsStore = defineStore('skills', () => {
 
       await invoke('create_skill_symlink', {
         sourcePath: skill.path,
-        targetProjectPath
+        targetProjectPath,
+        linkStyle
       })
 
       // Recharger les skills pour voir le nouveau symlink
@@ -274,6 +277,26 @@ export const useSkillsStore = defineStore('skills', () => {
     }
   }
 
+  async function loadUsage(projectPaths: string[]) {
+    try {
+      const [skillUsage, commandUsage] = await Promise.all([
+        invoke<Record<string, UsageCounter>>('skill_usage', { projectPaths }),
+        invoke<Record<string, UsageCounter>>('command_usage', { projectPaths }),
+      ])
+      skills.value = skills.value.map(s => withUsage(s, skillUsage[s.id]))
+      commands.value = commands.value.map(c => withUsage(c, commandUsage[c.id]))
+    } catch (e) {
+      // L'analyse des sessions est facultative : les listes restent affichées
+      if (import.meta.env.DEV) {
+        console.error('Failed to load usage:', e)
+      }
+    }
+  }
+
+  function withUsage<T extends Skill | Command>(item: T, usage?: UsageCounter): T {
+    return usage ? { ...item, usage_count: usage.count, last_used: usage.last_used } : item
+  }
+
   async function analyzeAllSkills() {
     for (const skill of skills.value) {
       try {
@@ -354,5 +377,6 @@ export const useSkillsStore = defineStore('skills', () => {
     updateSkill,
     analyzeSkill,
     analyzeAllSkills,
+    loadUsage,
   }
 })
@@ -50,3 +50,46 @@ export const HOOK_EVENTS = [
   'SubagentStop',
   'PreCompact',
 ]
+
+export type RiskLevel = 'none' | 'low' | 'medium' | 'high' | 'critical'
+
+export interface HookRisk {
+  code: string
+  level: RiskLevel
+  message: string
+  excerpt: string
+}
+
+export interface HookAudit {
+  event: string
+  matcher?: string
+  command: string
+  risk_level: RiskLevel
+  risks: HookRisk[]
+}
+
+export interface HookScopeAudit {
+  label: string
+  path: string
+  risk_level: RiskLevel
+  hooks: HookAudit[]
+}
+
+// Revue des hooks et serveurs MCP introduits par un projet
+export interface ConfigChange {
+  kind: 'hook' | 'mcp'
+  name: string
+  status: 'added' | 'changed' | 'removed'
+}
+
+export interface ProjectReview {
+  project_path: string
+  name: string
+  hash: string
+  approved_hash?: string
+  approved_at?: number
+  pending: boolean
+  quarantined: boolean
+  changes: ConfigChange[]
+  diff: string
+}
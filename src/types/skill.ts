@@ -13,6 +13,53 @@ export interface Skill {
   content: string
   last_modified: number
   linked_projects: string[]
+  trust_level: TrustLevel
+  usage_count: number
+  last_used?: number
+}
+
+export type TrustLevel = 'trusted' | 'unverified' | 'untrusted'
+
+export interface Command extends Omit<Skill, 'trust_level'> {
+  namespace?: string
+  invocation: string
+  scope: 'user' | 'project'
+  argument_hint?: string
+  allowed_tools: string[]
+  model?: string
+  disable_model_invocation: boolean
+  extra_frontmatter: Record<string, unknown>
+}
+
+export interface CommandCollision {
+  invocation: string
+  project_name: string
+  project_path: string
+  user_command_path: string
+  project_command_path: string
+}
+
+export interface CommandFields {
+  description?: string
+  argument_hint?: string
+  allowed_tools: string[]
+  model?: string
+  disable_model_invocation: boolean
+  content: string
+}
+
+export interface ProjectInventory {
+  own_skills: number
+  linked_skills: number
+  commands: number
+  agents: number
+  hooks: number
+  mcp_servers: number
+  permission_rules: number
+  has_claude_md: boolean
+  has_claude_local_md: boolean
+  git_branch?: string
+  git_dirty?: boolean
 }
 
 export interface Project {
@@ -20,17 +67,61 @@ export interface Project {
   path: string
   skills_path: string
   skill_count: number
+  inventory: ProjectInventory
+  pending_review: boolean
+  missing: boolean
+  last_used?: number
 }
 
 export interface Config {
   global_skills_path: string
   projects_base_path: string
   custom_project_paths: string[]
+  templates_path?: string
+  vault_path?: string
+  env_file_path?: string
+  approvals_path?: string
+  profiles?: Profile[]
+  applied_profiles_path?: string
+  scan_exclude_globs?: string[]
+  scan_depths?: Record<string, number>
+  scan_cache_path?: string
+  discover_claude_projects?: boolean
+  usage_cache_path?: string
+  skill_provenance_path?: string
+}
+
+// Événement "scan-progress"
+export interface ScanProgress {
+  base_path: string
+  scanned_dirs: number
+  found_projects: number
+  cached: boolean
+  done: boolean
+}
+
+// Profil : ensemble de skills, commandes, hooks, serveurs MCP et permissions
+export interface Profile {
+  name: string
+  description: string
+  mode: 'link' | 'copy'
+  skills: string[]
+  commands: string[]
+  hooks: Record<string, unknown>
+  mcp_servers: Record<string, unknown>
+  permissions: { allow: string[]; deny: string[] }
+}
+
+export interface ProfileItem {
+  kind: 'skill' | 'command' | 'hook' | 'mcp' | 'permission'
+  name: string
+  status: 'present' | 'missing' | 'drifted' | 'applied' | 'unchanged' | 'conflict' | 'removed' | 'absent' | 'kept'
+  message?: string
 }
 
 export interface SkillIssue {
   severity: 'error' | 'warning' | 'info'
-  category: 'description' | 'naming' | 'structure' | 'instructions' | 'frontmatter' | 'optimization'
+  category: 'description' | 'naming' | 'structure' | 'instructions' | 'frontmatter' | 'optimization' | 'scripts' | 'security'
   code: string
   message: string
   current_value?: string
@@ -56,6 +147,8 @@ export interface SkillStructure {
   folders: string[]
   total_size_kb: number
   instruction_word_count: number
+  frontmatter_tokens: number
+  body_tokens: number
 }
 
 export interface SkillAnalysis {
@@ -69,3 +162,123 @@ export interface SkillAnalysis {
   issues: SkillIssue[]
   suggestions: SkillSuggestion[]
 }
+
+export interface CommandBashLine {
+  line: number
+  command: string
+  allowed: boolean
+  output?: string
+  exit_code?: number
+  error?: string
+}
+
+export interface CommandFileReference {
+  reference: string
+  path: string
+  status: 'inlined' | 'missing' | 'too_large' | 'binary' | 'outside_project'
+  bytes: number
+}
+
+export interface CommandPreview {
+  invocation: string
+  arguments: string[]
+  prompt: string
+  bash: CommandBashLine[]
+  files: CommandFileReference[]
+  executed: boolean
+}
+
+export interface SkillFileNode {
+  name: string
+  relative_path: string
+  is_dir: boolean
+  size: number
+  binary: boolean
+  executable: boolean
+  children: SkillFileNode[]
+}
+
+export interface SkillFileContent {
+  relative_path: string
+  size: number
+  binary: boolean
+  executable: boolean
+  content?: string
+}
+
+export interface SecretFinding {
+  rule: string
+  description: string
+  scope: 'skill' | 'command' | 'settings' | 'mcp'
+  path: string
+  line?: number
+  json_pointer?: string
+  redacted: string
+}
+
+export interface SecretReport {
+  scanned_files: number
+  findings: SecretFinding[]
+}
+
+export interface ExternalizedSecret {
+  block: string
+  server: string
+  field: 'env' | 'headers'
+  key: string
+  variable: string
+}
+
+export interface MaterializedEnv {
+  variables: Record<string, string>
+  missing: string[]
+  servers: Record<string, unknown>
+}
+
+// Graphe des symlinks de projets
+export type LinkStyle = 'absolute' | 'relative'
+
+export interface LinkEntry {
+  kind: 'skill' | 'command'
+  link_path: string
+  project_name: string
+  project_path: string
+  raw_target: string
+  intended_target: string
+  canonical_target?: string
+  dangling: boolean
+  suggested_target?: string
+}
+
+export interface BulkLinkOutcome {
+  skill_path: string
+  project_path: string
+  status: 'linked' | 'replaced' | 'unlinked' | 'skipped' | 'failed' | 'rolled_back' | 'not_attempted'
+  message?: string
+}
+
+// Initialisation d'un projet
+export interface InitOptions {
+  mcp_json?: boolean
+  claude_md?: boolean
+  allow_stack_commands?: boolean
+}
+
+export interface StackCommand {
+  label: string
+  command: string
+}
+
+export interface ProjectStack {
+  name?: string
+  languages: string[]
+  frameworks: string[]
+  commands: StackCommand[]
+}
+
+export interface InitReport {
+  project: Project
+  stack: ProjectStack
+  created: string[]
+  skipped: string[]
+}
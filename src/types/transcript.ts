@@ -0,0 +1,80 @@
+// Résumé d'une session Claude Code
+export interface SessionSummary {
+  id: string
+  path: string
+  cwd?: string
+  started_at?: string
+  ended_at?: string
+  duration_secs?: number
+  message_count: number
+  model?: string
+  first_prompt?: string
+}
+
+// Bloc de contenu d'un message
+export type MessageBlock =
+  | { type: 'text'; text: string }
+  | { type: 'thinking'; text: string }
+  | { type: 'tool_use'; id: string; name: string; input: unknown }
+  | { type: 'tool_result'; tool_use_id: string; content: string; is_error: boolean }
+
+export interface TranscriptMessage {
+  index: number
+  uuid?: string
+  role: 'user' | 'assistant'
+  timestamp?: string
+  model?: string
+  is_meta: boolean
+  blocks: MessageBlock[]
+}
+
+// Payload de l'événement "session-messages"
+export interface SessionChunk {
+  session_id: string
+  messages: TranscriptMessage[]
+  done: boolean
+}
+
+// Résultat de search_sessions
+export interface SessionHit {
+  session_id: string
+  project_path?: string
+  message_index: number
+  role: string
+  timestamp?: string
+  snippet: string
+}
+
+// Analyse d'utilisation (usage_analytics, skill_usage, command_usage)
+export interface UsageCounter {
+  count: number
+  last_used?: number
+}
+
+export interface DailyUsage {
+  skill_loads: number
+  command_invocations: number
+  tool_uses: number
+  mcp_calls: number
+}
+
+export interface UsageStats {
+  sessions: number
+  skills: Record<string, UsageCounter>
+  skill_reads: Record<string, UsageCounter>
+  commands: Record<string, UsageCounter>
+  tools: Record<string, UsageCounter>
+  mcp_tools: Record<string, UsageCounter>
+  daily: Record<string, DailyUsage>
+}
+
+export interface ProjectUsage {
+  project_path: string
+  stats: UsageStats
+}
+
+export interface UsageReport {
+  projects: ProjectUsage[]
+  total: UsageStats
+  unused_skills: string[]
+}